@@ -0,0 +1,280 @@
+#[cfg(feature = "clans")]
+use crate::api::clans::models::{APIClan, APIClanCapitalRaidSeason, APIClanMember, APIClanWar, APIClanWarLeagueGroup, APIClanWarLogEntry};
+#[cfg(feature = "clans")]
+use crate::api::clans::search::ClanSearchOptions;
+#[cfg(any(feature = "clans", feature = "leagues", feature = "locations", feature = "labels"))]
+use crate::api::common::models::APIPagedResponse;
+#[cfg(any(feature = "clans", feature = "leagues", feature = "locations", feature = "labels"))]
+use crate::api::common::pagination::PaginationOptions;
+use crate::api::common::pagination::{PaginationCheckpoint, PaginationDirection};
+use crate::api::common::paginator::Paginator;
+#[cfg(feature = "goldpass")]
+use crate::api::goldpass::models::APIGoldPassSeason;
+#[cfg(feature = "labels")]
+use crate::api::labels::models::APILabel;
+#[cfg(feature = "leagues")]
+use crate::api::leagues::models::{APIBuilderBaseLeague, APICapitalLeague, APILeague, APILeagueSeason, APIWarLeague};
+#[cfg(any(feature = "locations", feature = "leagues"))]
+use crate::api::locations::models::APIPlayerRanking;
+#[cfg(feature = "locations")]
+use crate::api::locations::models::{APIClanBuilderBaseRanking, APIClanCapitalRanking, APIClanRanking, APILocation, APIPlayerBuilderBaseRanking};
+#[cfg(feature = "players")]
+use crate::api::players::models::APIPlayer;
+use crate::api::response::Response;
+use crate::api::rest_manager::{RestManager, RestManagerConfig};
+use crate::errors::{Error, Result};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::runtime::Runtime;
+
+/// Synchronous version of `RestManager` that runs its calls on a private runtime.
+///
+/// Methods block the current thread, so they must not be called from async code;
+/// use the `RestManager` returned by `BlockingRestManager::rest_manager` there instead.
+#[derive(Debug, Clone)]
+pub struct BlockingRestManager {
+    rest_manager: RestManager,
+    // shared between clones, dropped with the last of them
+    runtime: Arc<Runtime>,
+}
+
+impl BlockingRestManager {
+    pub fn new(token: impl AsRef<str>) -> Result<Self> {
+        Self::with_config(token, RestManagerConfig::default())
+    }
+
+    pub fn with_config(token: impl AsRef<str>, config: RestManagerConfig) -> Result<Self> {
+        Self::with_tokens([token], config)
+    }
+
+    /// Creates a `BlockingRestManager` that distributes requests across several API tokens, see `RestManager::with_tokens`.
+    pub fn with_tokens<I, S>(tokens: I, config: RestManagerConfig) -> Result<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self::from_rest_manager(RestManager::with_tokens(tokens, config)?)
+    }
+
+    /// Wraps an existing `RestManager`, sharing its tokens, cache and statistics.
+    pub fn from_rest_manager(rest_manager: RestManager) -> Result<Self> {
+        Ok(Self { rest_manager, runtime: Arc::new(new_runtime()?) })
+    }
+
+    /// Returns the underlying `RestManager`, e.g. for `RestManager::cache_stats` or `RestManager::key_health`.
+    pub fn rest_manager(&self) -> &RestManager {
+        &self.rest_manager
+    }
+
+    /// Blocking version of `RestManager::get_raw`.
+    pub fn get_raw<T>(&self, url: impl AsRef<str>, parameters: Option<HashMap<String, String>>) -> Result<Response<T>>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        self.runtime.block_on(self.rest_manager.get_raw(url, parameters))
+    }
+}
+
+fn new_runtime() -> Result<Runtime> {
+    tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|err| Error::Transport(err.into()))
+}
+
+/// Implements blocking versions of `RestManager` endpoint methods with the same arguments.
+macro_rules! blocking_methods {
+    ($(#[$attr:meta])* $(fn $name:ident(&self $(, $arg:ident: $arg_type:ty)*) -> $result:ty;)*) => {
+        $(#[$attr])*
+        impl BlockingRestManager {
+            $(
+                #[doc = concat!("Blocking version of `RestManager::", stringify!($name), "`.")]
+                pub fn $name(&self $(, $arg: $arg_type)*) -> $result {
+                    self.runtime.block_on(self.rest_manager.$name($($arg),*))
+                }
+            )*
+        }
+    };
+}
+
+/// Implements blocking versions of the `*_paginated` methods of `RestManager`.
+macro_rules! blocking_paginators {
+    ($(#[$attr:meta])* $(fn $name:ident(&self $(, $arg:ident: $arg_type:ty)*) -> $result:ty;)*) => {
+        $(#[$attr])*
+        impl BlockingRestManager {
+            $(
+                #[doc = concat!("Blocking version of `RestManager::", stringify!($name), "`.")]
+                pub fn $name(&self $(, $arg: $arg_type)*) -> $result {
+                    IntoBlocking::into_blocking(self.rest_manager.$name($($arg),*), &self.runtime)
+                }
+            )*
+        }
+    };
+}
+
+blocking_methods! {
+    #[cfg(feature = "clans")]
+    fn clans(&self, search_options: ClanSearchOptions, pagination_options: PaginationOptions) -> Result<APIPagedResponse<APIClan>>;
+    fn clan_info(&self, tag: impl AsRef<str>) -> Result<APIClan>;
+    fn clan_members(&self, tag: impl AsRef<str>, pagination_options: PaginationOptions) -> Result<APIPagedResponse<APIClanMember>>;
+    fn war_log(&self, tag: impl AsRef<str>, pagination_options: PaginationOptions) -> Result<APIPagedResponse<APIClanWarLogEntry>>;
+    fn current_war(&self, tag: impl AsRef<str>) -> Result<APIClanWar>;
+    fn clan_war_league_group(&self, tag: impl AsRef<str>) -> Result<APIClanWarLeagueGroup>;
+    fn clan_war_league_war(&self, war_tag: impl AsRef<str>) -> Result<APIClanWar>;
+    fn clan_capital_raid_seasons(&self, tag: impl AsRef<str>, pagination_options: PaginationOptions) -> Result<APIPagedResponse<APIClanCapitalRaidSeason>>;
+}
+
+blocking_methods! {
+    #[cfg(feature = "players")]
+    fn player(&self, tag: impl AsRef<str>) -> Result<APIPlayer>;
+    fn verify(&self, tag: impl AsRef<str>, token: impl AsRef<str>) -> Result<bool>;
+}
+
+blocking_methods! {
+    #[cfg(feature = "leagues")]
+    fn leagues(&self, pagination_options: PaginationOptions) -> Result<APIPagedResponse<APILeague>>;
+    fn league_info(&self, league_id: impl AsRef<str>) -> Result<APILeague>;
+    fn builder_base_leagues(&self, pagination_options: PaginationOptions) -> Result<APIPagedResponse<APIBuilderBaseLeague>>;
+    fn builder_base_league_info(&self, league_id: impl AsRef<str>) -> Result<APIBuilderBaseLeague>;
+    fn league_seasons(&self, league_id: impl AsRef<str>, pagination_options: PaginationOptions) -> Result<APIPagedResponse<APILeagueSeason>>;
+    fn league_season_rankings(&self, league_id: impl AsRef<str>, season_id: impl AsRef<str>, pagination_options: PaginationOptions) -> Result<APIPagedResponse<APIPlayerRanking>>;
+    fn war_leagues(&self, pagination_options: PaginationOptions) -> Result<APIPagedResponse<APIWarLeague>>;
+    fn war_league_info(&self, league_id: impl AsRef<str>) -> Result<APIWarLeague>;
+    fn capital_leagues(&self, pagination_options: PaginationOptions) -> Result<APIPagedResponse<APICapitalLeague>>;
+    fn capital_league_info(&self, league_id: impl AsRef<str>) -> Result<APICapitalLeague>;
+}
+
+blocking_methods! {
+    #[cfg(feature = "locations")]
+    fn locations(&self, pagination_options: PaginationOptions) -> Result<APIPagedResponse<APILocation>>;
+    fn location_info(&self, location_id: impl AsRef<str>) -> Result<APILocation>;
+    fn players_rankings(&self, location_id: impl AsRef<str>, pagination_options: PaginationOptions) -> Result<APIPagedResponse<APIPlayerRanking>>;
+    fn clans_rankings(&self, location_id: impl AsRef<str>, pagination_options: PaginationOptions) -> Result<APIPagedResponse<APIClanRanking>>;
+    fn players_builder_base_rankings(&self, location_id: impl AsRef<str>, pagination_options: PaginationOptions) -> Result<APIPagedResponse<APIPlayerBuilderBaseRanking>>;
+    fn clans_builder_base_rankings(&self, location_id: impl AsRef<str>, pagination_options: PaginationOptions) -> Result<APIPagedResponse<APIClanBuilderBaseRanking>>;
+    fn capitals_rankings(&self, location_id: impl AsRef<str>, pagination_options: PaginationOptions) -> Result<APIPagedResponse<APIClanCapitalRanking>>;
+}
+
+blocking_methods! {
+    #[cfg(feature = "labels")]
+    fn player_labels(&self, pagination_options: PaginationOptions) -> Result<APIPagedResponse<APILabel>>;
+    fn clan_labels(&self, pagination_options: PaginationOptions) -> Result<APIPagedResponse<APILabel>>;
+}
+
+blocking_methods! {
+    #[cfg(feature = "goldpass")]
+    fn goldpass(&self) -> Result<APIGoldPassSeason>;
+}
+
+blocking_paginators! {
+    #[cfg(feature = "clans")]
+    fn clans_paginated(&self, search_options: ClanSearchOptions) -> BlockingPaginator<APIClan>;
+    fn clan_members_paginated(&self, tag: impl AsRef<str>) -> Result<BlockingPaginator<APIClanMember>>;
+    fn war_log_paginated(&self, tag: impl AsRef<str>) -> Result<BlockingPaginator<APIClanWarLogEntry>>;
+    fn clan_capital_raid_seasons_paginated(&self, tag: impl AsRef<str>) -> Result<BlockingPaginator<APIClanCapitalRaidSeason>>;
+}
+
+blocking_paginators! {
+    #[cfg(feature = "leagues")]
+    fn league_season_rankings_paginated(&self, league_id: impl AsRef<str>, season_id: impl AsRef<str>) -> BlockingPaginator<APIPlayerRanking>;
+}
+
+blocking_paginators! {
+    #[cfg(feature = "locations")]
+    fn players_rankings_paginated(&self, location_id: impl AsRef<str>) -> BlockingPaginator<APIPlayerRanking>;
+    fn clans_rankings_paginated(&self, location_id: impl AsRef<str>) -> BlockingPaginator<APIClanRanking>;
+    fn players_builder_base_rankings_paginated(&self, location_id: impl AsRef<str>) -> BlockingPaginator<APIPlayerBuilderBaseRanking>;
+    fn clans_builder_base_rankings_paginated(&self, location_id: impl AsRef<str>) -> BlockingPaginator<APIClanBuilderBaseRanking>;
+    fn capitals_rankings_paginated(&self, location_id: impl AsRef<str>) -> BlockingPaginator<APIClanCapitalRanking>;
+}
+
+blocking_paginators! {
+    #[cfg(feature = "labels")]
+    fn player_labels_paginated(&self) -> BlockingPaginator<APILabel>;
+    fn clan_labels_paginated(&self) -> BlockingPaginator<APILabel>;
+}
+
+/// Synchronous version of `Paginator`, an `Iterator` over all items of a paged endpoint.
+#[derive(Debug)]
+pub struct BlockingPaginator<T> {
+    paginator: Paginator<T>,
+    runtime: Arc<Runtime>,
+}
+
+impl<T> BlockingPaginator<T>
+where
+    T: serde::de::DeserializeOwned + Send + 'static,
+{
+    /// See `Paginator::page_size`.
+    pub fn page_size(self, page_size: u32) -> Self {
+        Self { paginator: self.paginator.page_size(page_size), ..self }
+    }
+
+    /// See `Paginator::max_items`.
+    pub fn max_items(self, max_items: usize) -> Self {
+        Self { paginator: self.paginator.max_items(max_items), ..self }
+    }
+
+    /// See `Paginator::direction`.
+    pub fn direction(self, direction: PaginationDirection) -> Self {
+        Self { paginator: self.paginator.direction(direction), ..self }
+    }
+
+    /// See `Paginator::start_at`.
+    pub fn start_at(self, cursor: impl AsRef<str>) -> Self {
+        Self { paginator: self.paginator.start_at(cursor), ..self }
+    }
+
+    /// See `Paginator::resume`.
+    pub fn resume(self, checkpoint: PaginationCheckpoint) -> Result<Self> {
+        Ok(Self { paginator: self.paginator.resume(checkpoint)?, ..self })
+    }
+
+    /// See `Paginator::checkpoint`.
+    pub fn checkpoint(&self) -> PaginationCheckpoint {
+        self.paginator.checkpoint()
+    }
+
+    /// See `Paginator::collect_all`.
+    pub fn collect_all(self) -> Result<Vec<T>> {
+        self.runtime.block_on(self.paginator.collect_all())
+    }
+}
+
+impl<T> Iterator for BlockingPaginator<T>
+where
+    T: serde::de::DeserializeOwned + Send + 'static,
+{
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.runtime.block_on(self.paginator.next())
+    }
+}
+
+/// Wraps the paginators returned by `RestManager` with the runtime of a `BlockingRestManager`.
+// only the groups with paged endpoints use it
+#[cfg(any(feature = "clans", feature = "leagues", feature = "locations", feature = "labels"))]
+trait IntoBlocking {
+    type Output;
+
+    fn into_blocking(self, runtime: &Arc<Runtime>) -> Self::Output;
+}
+
+#[cfg(any(feature = "clans", feature = "leagues", feature = "locations", feature = "labels"))]
+impl<T> IntoBlocking for Paginator<T> {
+    type Output = BlockingPaginator<T>;
+
+    fn into_blocking(self, runtime: &Arc<Runtime>) -> Self::Output {
+        BlockingPaginator { paginator: self, runtime: runtime.clone() }
+    }
+}
+
+#[cfg(any(feature = "clans", feature = "leagues", feature = "locations", feature = "labels"))]
+impl<T> IntoBlocking for Result<Paginator<T>> {
+    type Output = Result<BlockingPaginator<T>>;
+
+    fn into_blocking(self, runtime: &Arc<Runtime>) -> Self::Output {
+        self.map(|paginator| paginator.into_blocking(runtime))
+    }
+}
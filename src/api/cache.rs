@@ -0,0 +1,130 @@
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::SystemTime;
+
+/// Deserialized JSON of a response, stored until the API's `max-age` expires.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CacheEntry {
+    pub value: serde_json::Value,
+    pub expires_at: SystemTime,
+}
+
+impl CacheEntry {
+    /// Checks whether the entry is no longer fresh.
+    pub fn is_expired(&self) -> bool {
+        self.expires_at <= SystemTime::now()
+    }
+}
+
+/// Storage for cached responses, keyed by URL and query parameters.
+///
+/// Implement it to keep responses in an external store; `InMemoryCache` is used otherwise.
+#[async_trait]
+pub trait Cache: fmt::Debug + Send + Sync {
+    /// Returns the entry stored for the key, expired or not.
+    async fn get(&self, key: &str) -> Option<CacheEntry>;
+
+    /// Stores the entry, replacing the previous one.
+    async fn insert(&self, key: String, entry: CacheEntry);
+
+    /// Removes the entry stored for the key.
+    async fn remove(&self, key: &str);
+}
+
+/// `Cache` that keeps entries in a `HashMap` of the current process.
+#[derive(Debug, Default)]
+pub struct InMemoryCache {
+    entries: Mutex<HashMap<String, CacheEntry>>,
+}
+
+impl InMemoryCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of stored entries, including expired ones.
+    pub fn len(&self) -> usize {
+        self.entries.lock().expect("Cache lock should not be poisoned").len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes all expired entries.
+    pub fn purge_expired(&self) {
+        self.entries.lock().expect("Cache lock should not be poisoned").retain(|_, entry| !entry.is_expired());
+    }
+}
+
+#[async_trait]
+impl Cache for InMemoryCache {
+    async fn get(&self, key: &str) -> Option<CacheEntry> {
+        self.entries.lock().expect("Cache lock should not be poisoned").get(key).cloned()
+    }
+
+    async fn insert(&self, key: String, entry: CacheEntry) {
+        self.entries.lock().expect("Cache lock should not be poisoned").insert(key, entry);
+    }
+
+    async fn remove(&self, key: &str) {
+        self.entries.lock().expect("Cache lock should not be poisoned").remove(key);
+    }
+}
+
+/// How a single request uses the cache.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CacheMode {
+    /// Fresh entries are returned, responses are stored.
+    #[default]
+    Default,
+    /// The cache is neither read nor written.
+    Bypass,
+    /// The request is always sent, its response replaces the stored entry.
+    ForceRefresh,
+}
+
+/// Number of requests served from and missed by the cache.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct CacheCounters {
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl CacheCounters {
+    pub(crate) fn hit(&self) {
+        self.hits.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn miss(&self) {
+        self.misses.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Builds the cache key from the URL and the query parameters sorted by name.
+pub(crate) fn cache_key(url: &str, parameters: &HashMap<String, String>) -> String {
+    let mut parameters: Vec<_> = parameters.iter().collect();
+    parameters.sort();
+    let query: Vec<_> = parameters.iter().map(|(name, value)| format!("{name}={value}")).collect();
+    if query.is_empty() {
+        url.to_string()
+    } else {
+        format!("{url}?{}", query.join("&"))
+    }
+}
@@ -1,3 +1,4 @@
 pub mod models;
+#[cfg(all(feature = "http", feature = "clans"))]
 pub mod requests;
 pub mod search;
\ No newline at end of file
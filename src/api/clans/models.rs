@@ -1,4 +1,7 @@
+use crate::api::common::enums::api_enum;
 use crate::api::common::models::APIBadge;
+use crate::api::common::tags::{ClanTag, PlayerTag, WarTag};
+use crate::api::common::timestamp::APITimestamp;
 use crate::api::labels::models::APILabel;
 use crate::api::leagues::models::{APIBuilderBaseLeague, APICapitalLeague, APILeague, APIWarLeague};
 use crate::api::locations::models::APILocation;
@@ -11,7 +14,7 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct APIClan {
-    pub tag: String,
+    pub tag: ClanTag,
     pub name: String,
     pub r#type: APIClanType,
     pub description: Option<String>,
@@ -22,7 +25,8 @@ pub struct APIClan {
     pub clan_points: i64,
     pub clan_builder_base_points: i64,
     pub clan_capital_points: i64,
-    pub capital_league: APICapitalLeague,
+    // missing in some older or edge-case responses
+    pub capital_league: Option<APICapitalLeague>,
     pub required_trophies: i64,
     pub war_frequency: APIWarFrequency,
     pub war_win_streak: i64,
@@ -40,39 +44,32 @@ pub struct APIClan {
     pub chat_language: Option<APILanguage>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
-pub enum APIClanType {
-    #[serde(rename = "open")]
-    Open,
-    #[serde(rename = "inviteOnly")]
-    InviteOnly,
-    #[serde(rename = "closed")]
-    Closed,
+api_enum! {
+    pub enum APIClanType {
+        Open = "open",
+        InviteOnly = "inviteOnly",
+        Closed = "closed",
+    }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
-pub enum APIWarFrequency {
-    #[serde(rename = "unknown")]
-    Unknown,
-    #[serde(rename = "never")]
-    Never,
-    #[serde(rename = "lessThanOncePerWeek")]
-    LessThanOncePerWeek,
-    #[serde(rename = "oncePerWeek")]
-    OncePerWeek,
-    #[serde(rename = "moreThanOncePerWeek")]
-    MoreThanOncePerWeek,
-    #[serde(rename = "always")]
-    Always,
-    // only used in search
-    #[serde(rename = "any")]
-    Any,
+api_enum! {
+    pub enum APIWarFrequency {
+        // clans that did not set it, kept apart from `Unknown` for values added to the API later
+        NotSet = "unknown",
+        Never = "never",
+        LessThanOncePerWeek = "lessThanOncePerWeek",
+        OncePerWeek = "oncePerWeek",
+        MoreThanOncePerWeek = "moreThanOncePerWeek",
+        Always = "always",
+        // only used in search
+        Any = "any",
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct APIClanMember {
-    pub tag: String,
+    pub tag: PlayerTag,
     pub name: String,
     pub role: APIClanMemberRole,
     pub town_hall_level: i64,
@@ -86,21 +83,18 @@ pub struct APIClanMember {
     pub donations_received: i64,
     // if player doesn't change default house it will be None
     pub player_house: Option<APIPlayerHouse>,
-    pub builder_base_league: APIBuilderBaseLeague,
+    // missing in some older or edge-case responses
+    pub builder_base_league: Option<APIBuilderBaseLeague>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
-pub enum APIClanMemberRole {
-    #[serde(rename = "notMember")]
-    NotMember,
-    #[serde(rename = "member")]
-    Member,
-    #[serde(rename = "admin")]
-    Admin,
-    #[serde(rename = "coLeader")]
-    CoLeader,
-    #[serde(rename = "leader")]
-    Leader,
+api_enum! {
+    pub enum APIClanMemberRole {
+        NotMember = "notMember",
+        Member = "member",
+        Admin = "admin",
+        CoLeader = "coLeader",
+        Leader = "leader",
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
@@ -135,37 +129,85 @@ pub struct APIClanWar {
     pub team_size: Option<i64>,
     pub attacks_per_member: Option<i64>,
     pub battle_modifier: Option<APIBattleModifier>,
-    pub preparation_start_time: Option<String>,
-    pub start_time: Option<String>,
-    pub end_time: Option<String>,
+    pub preparation_start_time: Option<APITimestamp>,
+    pub start_time: Option<APITimestamp>,
+    pub end_time: Option<APITimestamp>,
     pub clan: APIWarClan,
     pub opponent: APIWarClan,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
-pub enum APIWarState {
-    #[serde(rename = "notInWar")]
+/// Phase of a war at a specific time, derived from its start and end times.
+#[cfg(feature = "chrono")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WarPhase {
     NotInWar,
-    #[serde(rename = "preparation")]
     Preparation,
-    #[serde(rename = "inWar")]
-    InWar,
-    #[serde(rename = "warEnded")]
-    WarEnded,
+    Battle,
+    Ended,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
-pub enum APIBattleModifier {
-    #[serde(rename = "none")]
-    None,
-    #[serde(rename = "hardMode")]
-    HardMode,
+#[cfg(feature = "chrono")]
+impl APIClanWar {
+    /// Determines the phase of the war at the given time.
+    ///
+    /// # Arguments
+    /// * `now` - The time to determine the phase at.
+    pub fn phase_at(&self, now: chrono::DateTime<chrono::Utc>) -> WarPhase {
+        if self.state == APIWarState::NotInWar {
+            return WarPhase::NotInWar;
+        }
+        match (&self.start_time, &self.end_time) {
+            (Some(start_time), _) if now < start_time.datetime() => WarPhase::Preparation,
+            (_, Some(end_time)) if now < end_time.datetime() => WarPhase::Battle,
+            (Some(_), Some(_)) => WarPhase::Ended,
+            // without times the state returned by the API is the best guess
+            _ => match self.state {
+                APIWarState::Preparation => WarPhase::Preparation,
+                APIWarState::InWar => WarPhase::Battle,
+                _ => WarPhase::Ended,
+            },
+        }
+    }
+
+    /// Returns the time left until the end of the current phase at the given time.
+    ///
+    /// # Returns
+    /// The time until the battle starts during preparation, until the war ends during the battle, `None` otherwise.
+    pub fn time_remaining_at(&self, now: chrono::DateTime<chrono::Utc>) -> Option<chrono::TimeDelta> {
+        let until = match self.phase_at(now) {
+            WarPhase::Preparation => self.start_time.as_ref()?,
+            WarPhase::Battle => self.end_time.as_ref()?,
+            WarPhase::NotInWar | WarPhase::Ended => return None,
+        };
+        Some(until.datetime() - now)
+    }
+
+    /// Returns the time left until the end of the current phase, see `APIClanWar::time_remaining_at`.
+    pub fn time_remaining(&self) -> Option<chrono::TimeDelta> {
+        self.time_remaining_at(chrono::Utc::now())
+    }
+}
+
+api_enum! {
+    pub enum APIWarState {
+        NotInWar = "notInWar",
+        Preparation = "preparation",
+        InWar = "inWar",
+        WarEnded = "warEnded",
+    }
+}
+
+api_enum! {
+    pub enum APIBattleModifier {
+        None = "none",
+        HardMode = "hardMode",
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct APIWarClan {
-    pub tag: Option<String>,
+    pub tag: Option<ClanTag>,
     pub name: Option<String>,
     pub badge_urls: APIBadge,
     pub clan_level: i64,
@@ -179,7 +221,7 @@ pub struct APIWarClan {
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct APIClanWarMember {
-    pub tag: String,
+    pub tag: PlayerTag,
     pub name: String,
     pub townhall_level: i64,
     pub map_position: i64,
@@ -191,8 +233,8 @@ pub struct APIClanWarMember {
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct APIClanWarAttack {
-    pub attacker_tag: String,
-    pub defender_tag: String,
+    pub attacker_tag: PlayerTag,
+    pub defender_tag: PlayerTag,
     pub stars: i64,
     pub destruction_percentage: i64,
     pub order: i64,
@@ -203,22 +245,21 @@ pub struct APIClanWarAttack {
 #[serde(rename_all = "camelCase")]
 pub struct APIClanWarLogEntry {
     pub result: Option<APIWarResult>,
-    pub end_time: String,
+    pub end_time: APITimestamp,
     pub team_size: i64,
     pub attacks_per_member: i64,
-    pub battle_modifier: APIBattleModifier,
+    // missing in some older or edge-case responses
+    pub battle_modifier: Option<APIBattleModifier>,
     pub clan: APIWarClan,
     pub opponent: APIWarClan,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
-pub enum APIWarResult {
-    #[serde(rename = "lose")]
-    Lose,
-    #[serde(rename = "win")]
-    Win,
-    #[serde(rename = "tie")]
-    Tie,
+api_enum! {
+    pub enum APIWarResult {
+        Lose = "lose",
+        Win = "win",
+        Tie = "tie",
+    }
 }
 
 // ---------- Clan War League ----------
@@ -232,22 +273,19 @@ pub struct APIClanWarLeagueGroup {
     pub rounds: Vec<APIClanWarLeagueRound>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
-pub enum APIClanWarLeagueState {
-    #[serde(rename = "notInWar")]
-    NotInWar,
-    #[serde(rename = "preparation")]
-    Preparation,
-    #[serde(rename = "inWar")]
-    InWar,
-    #[serde(rename = "ended")]
-    Ended,
+api_enum! {
+    pub enum APIClanWarLeagueState {
+        NotInWar = "notInWar",
+        Preparation = "preparation",
+        InWar = "inWar",
+        Ended = "ended",
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct APIClanWarLeagueClan {
-    pub tag: String,
+    pub tag: ClanTag,
     pub name: String,
     pub clan_level: i64,
     pub badge_urls: APIBadge,
@@ -257,7 +295,7 @@ pub struct APIClanWarLeagueClan {
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct APIClanWarLeagueClanMember {
-    pub tag: String,
+    pub tag: PlayerTag,
     #[serde(rename = "townHallLevel")]
     pub townhall_level: i64,
     pub name: String,
@@ -266,7 +304,7 @@ pub struct APIClanWarLeagueClanMember {
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct APIClanWarLeagueRound {
-    pub war_tags: Vec<String>,
+    pub war_tags: Vec<WarTag>,
 }
 
 // ---------- Clan Capital Raid Season ----------
@@ -275,8 +313,8 @@ pub struct APIClanWarLeagueRound {
 #[serde(rename_all = "camelCase")]
 pub struct APIClanCapitalRaidSeason {
     pub state: APIClanCapitalRaidSeasonState,
-    pub start_time: String,
-    pub end_time: String,
+    pub start_time: APITimestamp,
+    pub end_time: APITimestamp,
     pub capital_total_loot: i64,
     pub raids_completed: i64,
     pub total_attacks: i64,
@@ -289,18 +327,17 @@ pub struct APIClanCapitalRaidSeason {
     pub defense_log: Vec<APIClanCapitalRaidSeasonDefenseLogEntry>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
-pub enum APIClanCapitalRaidSeasonState {
-    #[serde(rename = "ongoing")]
-    Ongoing,
-    #[serde(rename = "ended")]
-    Ended,
+api_enum! {
+    pub enum APIClanCapitalRaidSeasonState {
+        Ongoing = "ongoing",
+        Ended = "ended",
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct APIClanCapitalRaidSeasonMember {
-    pub tag: String,
+    pub tag: PlayerTag,
     pub name: String,
     pub attacks: i64,
     pub attack_limit: i64,
@@ -331,7 +368,7 @@ pub struct APIClanCapitalRaidSeasonDefenseLogEntry {
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct APIClanCapitalRaidSeasonClanInfo {
-    pub tag: String,
+    pub tag: ClanTag,
     pub name: String,
     pub level: i64,
     pub badge_urls: APIBadge,
@@ -362,6 +399,6 @@ pub struct APIClanCapitalRaidSeasonAttack {
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct APIClanCapitalRaidSeasonAttacker {
-    pub tag: String,
+    pub tag: PlayerTag,
     pub name: String,
 }
@@ -2,9 +2,11 @@ use crate::api::clans::models::{APIClan, APIClanCapitalRaidSeason, APIClanMember
 use crate::api::clans::search::ClanSearchOptions;
 use crate::api::common::models::APIPagedResponse;
 use crate::api::common::pagination::PaginationOptions;
-use crate::api::common::utils::normalize_tag;
+use crate::api::common::paginator::Paginator;
+use crate::api::common::tags::{ClanTag, WarTag};
 use crate::api::rest_manager::RestManager;
 use crate::errors::Result;
+use std::collections::HashMap;
 
 /// Macro to format the URL for clan-related requests.
 macro_rules! format_url {
@@ -29,6 +31,17 @@ impl RestManager {
         self.get(&url, parameters.into()).await
     }
 
+    /// Iterates over all clans matching the search options.
+    ///
+    /// # Arguments
+    /// * `search_options` - The filter parameters for searching clans.
+    ///
+    /// # Returns
+    /// `Paginator<APIClan>` that requests the pages lazily.
+    pub fn clans_paginated(&self, search_options: ClanSearchOptions) -> Paginator<APIClan> {
+        Paginator::new(self, "clans".to_string(), search_options.to_query_parameters())
+    }
+
     /// Retrieves clan information.
     ///
     /// # Arguments
@@ -37,7 +50,7 @@ impl RestManager {
     /// # Returns
     /// `Result` containing the `APIClan` if successful; if an error occurs, it contains an `Error`.
     pub async fn clan_info(&self, tag: impl AsRef<str>) -> Result<APIClan> {
-        let tag = normalize_tag(tag.as_ref());
+        let tag = ClanTag::parse(tag)?.encoded();
         let url = format_url!("{}", tag);
         self.get(&url, None).await
     }
@@ -51,11 +64,23 @@ impl RestManager {
     /// # Returns
     /// `Result` containing a `APIPagedResponse<APIClanMember>` if successful; if an error occurs, it contains an `Error`.
     pub async fn clan_members(&self, tag: impl AsRef<str>, pagination_options: PaginationOptions) -> Result<APIPagedResponse<APIClanMember>> {
-        let tag = normalize_tag(tag.as_ref());
+        let tag = ClanTag::parse(tag)?.encoded();
         let url = format_url!("{}/members", tag);
         self.get(&url, pagination_options.to_query_parameters().into()).await
     }
 
+    /// Iterates over all clan members.
+    ///
+    /// # Arguments
+    /// * `tag` - The tag of the clan.
+    ///
+    /// # Returns
+    /// `Result` containing a `Paginator<APIClanMember>` that requests the pages lazily if successful; if the tag is invalid, it contains an `Error`.
+    pub fn clan_members_paginated(&self, tag: impl AsRef<str>) -> Result<Paginator<APIClanMember>> {
+        let tag = ClanTag::parse(tag)?.encoded();
+        Ok(Paginator::new(self, format_url!("{}/members", tag), HashMap::new()))
+    }
+
     /// Retrieves clan's clan war log.
     ///
     /// # Arguments
@@ -65,11 +90,23 @@ impl RestManager {
     /// # Returns
     /// `Result` containing a `APIPagedResponse<APIClanWarLogEntry>` if successful; if an error occurs, it contains an `Error`.
     pub async fn war_log(&self, tag: impl AsRef<str>, pagination_options: PaginationOptions) -> Result<APIPagedResponse<APIClanWarLogEntry>> {
-        let tag = normalize_tag(tag.as_ref());
+        let tag = ClanTag::parse(tag)?.encoded();
         let url = format_url!("{}/warlog", tag);
         self.get(&url, pagination_options.to_query_parameters().into()).await
     }
 
+    /// Iterates over all entries of clan's clan war log.
+    ///
+    /// # Arguments
+    /// * `tag` - The tag of the clan.
+    ///
+    /// # Returns
+    /// `Result` containing a `Paginator<APIClanWarLogEntry>` that requests the pages lazily if successful; if the tag is invalid, it contains an `Error`.
+    pub fn war_log_paginated(&self, tag: impl AsRef<str>) -> Result<Paginator<APIClanWarLogEntry>> {
+        let tag = ClanTag::parse(tag)?.encoded();
+        Ok(Paginator::new(self, format_url!("{}/warlog", tag), HashMap::new()))
+    }
+
     /// Retrieves information about clan's current war.
     ///
     /// # Arguments
@@ -78,7 +115,7 @@ impl RestManager {
     /// # Returns
     /// `Result` containing the `APIClanWar` if successful; if an error occurs, it contains an `Error`.
     pub async fn current_war(&self, tag: impl AsRef<str>) -> Result<APIClanWar> {
-        let tag = normalize_tag(tag.as_ref());
+        let tag = ClanTag::parse(tag)?.encoded();
         let url = format_url!("{}/currentwar", tag);
         self.get(&url, None).await
     }
@@ -91,7 +128,7 @@ impl RestManager {
     /// # Returns
     /// `Result` containing a `APIClanWarLeagueGroup` if successful; if an error occurs, it contains an `Error`.
     pub async fn clan_war_league_group(&self, tag: impl AsRef<str>) -> Result<APIClanWarLeagueGroup> {
-        let tag = normalize_tag(tag.as_ref());
+        let tag = ClanTag::parse(tag)?.encoded();
         let url = format_url!("{}/currentwar/leaguegroup", tag);
         self.get(&url, None).await
     }
@@ -104,7 +141,7 @@ impl RestManager {
     /// # Returns
     /// `Result` containing the `APIClanWarLeagueGroup` if successful; if an error occurs, it contains an `Error`.
     pub async fn clan_war_league_war(&self, war_tag: impl AsRef<str>) -> Result<APIClanWar> {
-        let war_tag = normalize_tag(war_tag.as_ref());
+        let war_tag = WarTag::parse(war_tag)?.encoded();
         let url = format!("clanwarleagues/wars/{war_tag}");
         self.get(&url, None).await
     }
@@ -118,8 +155,20 @@ impl RestManager {
     /// # Returns
     /// `Result` containing a `APIPagedResponse<APIClanCapitalRaidSeason>` if successful; if an error occurs, it contains an `Error`.
     pub async fn clan_capital_raid_seasons(&self, tag: impl AsRef<str>, pagination_options: PaginationOptions) -> Result<APIPagedResponse<APIClanCapitalRaidSeason>> {
-        let tag = normalize_tag(tag.as_ref());
+        let tag = ClanTag::parse(tag)?.encoded();
         let url = format_url!("{}/capitalraidseasons", tag);
         self.get(&url, pagination_options.to_query_parameters().into()).await
     }
+
+    /// Iterates over all clan's capital raid seasons.
+    ///
+    /// # Arguments
+    /// * `tag` - The tag of the clan.
+    ///
+    /// # Returns
+    /// `Result` containing a `Paginator<APIClanCapitalRaidSeason>` that requests the pages lazily if successful; if the tag is invalid, it contains an `Error`.
+    pub fn clan_capital_raid_seasons_paginated(&self, tag: impl AsRef<str>) -> Result<Paginator<APIClanCapitalRaidSeason>> {
+        let tag = ClanTag::parse(tag)?.encoded();
+        Ok(Paginator::new(self, format_url!("{}/capitalraidseasons", tag), HashMap::new()))
+    }
 }
@@ -1,3 +1,9 @@
+#[cfg(any(feature = "clans", feature = "players"))]
+pub(crate) mod enums;
 pub mod models;
 pub mod pagination;
+#[cfg(feature = "http")]
+pub mod paginator;
+pub mod tags;
+pub mod timestamp;
 pub mod utils;
\ No newline at end of file
@@ -1,5 +1,43 @@
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+/// Direction the cursors of a paged endpoint are followed in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum PaginationDirection {
+    /// Pages are requested with the `after` cursor.
+    #[default]
+    Forward,
+    /// Pages are requested with the `before` cursor.
+    Backward,
+}
+
+/// Position of a `Paginator`, serializable to JSON to resume a long iteration later.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PaginationCheckpoint {
+    /// Path of the endpoint, e.g. `locations/32000006/rankings/players`.
+    pub endpoint: String,
+    // query parameters other than pagination, e.g. clan search filters
+    pub parameters: HashMap<String, String>,
+    pub direction: PaginationDirection,
+    /// Cursor of the page to request next; None for the first page.
+    pub cursor: Option<String>,
+    /// Number of items of that page that were already returned.
+    pub skip: usize,
+    /// Number of items returned since the iteration started.
+    pub items_seen: usize,
+    /// Whether every item was returned.
+    pub exhausted: bool,
+}
+
+impl PaginationCheckpoint {
+    /// Provides `PaginationOptions` that request the page of the checkpoint.
+    ///
+    /// The first `skip` items of the page have already been seen.
+    pub fn pagination_options(&self, limit: Option<u32>) -> PaginationOptions {
+        PaginationOptions::for_direction(self.direction, limit, self.cursor.clone())
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub struct PaginationOptions {
     pub limit: Option<u32>,
@@ -15,6 +53,14 @@ impl PaginationOptions {
         PaginationOptionsBuilder::new()
     }
 
+    /// Creates `PaginationOptions` that follow the cursor in the given direction.
+    pub fn for_direction(direction: PaginationDirection, limit: Option<u32>, cursor: Option<String>) -> Self {
+        match direction {
+            PaginationDirection::Forward => Self { limit, after: cursor, before: None },
+            PaginationDirection::Backward => Self { limit, after: None, before: cursor },
+        }
+    }
+
     /// Provides `HashMap<String, String>` for existing pagination options.
     pub fn to_query_parameters(self) -> HashMap<String, String> {
         let mut parameters = HashMap::new();
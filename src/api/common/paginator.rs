@@ -0,0 +1,205 @@
+use crate::api::common::models::APIPagedResponse;
+use crate::api::common::pagination::{PaginationCheckpoint, PaginationDirection, PaginationOptions};
+use crate::api::rest_manager::RestManager;
+use crate::errors::{Error, Result};
+use futures::Stream;
+use std::collections::{HashMap, VecDeque};
+
+/// Iterates over all items of a paged endpoint, following the cursors lazily.
+///
+/// Created by the `*_paginated` methods of `RestManager`, e.g. `RestManager::clan_members_paginated`.
+/// Its position can be saved with `Paginator::checkpoint` and restored with `Paginator::resume`.
+#[derive(Debug, Clone)]
+pub struct Paginator<T> {
+    rest_manager: RestManager,
+    url: String,
+    // parameters of the endpoint other than pagination, e.g. clan search filters
+    parameters: HashMap<String, String>,
+    page_size: Option<u32>,
+    max_items: Option<usize>,
+    direction: PaginationDirection,
+    // items of the current page that were not returned yet
+    items: VecDeque<T>,
+    // cursor the current page was requested with
+    page_cursor: Option<String>,
+    // items of the current page that were already returned
+    page_consumed: usize,
+    // cursor of the next page, if any
+    next_cursor: Option<String>,
+    // items of the next page returned before the checkpoint it was resumed from
+    pending_skip: usize,
+    exhausted: bool,
+    // set after an error, the failed page is kept in the checkpoint
+    failed: bool,
+    items_seen: usize,
+}
+
+impl<T> Paginator<T>
+where
+    T: serde::de::DeserializeOwned + Send + 'static,
+{
+    #[cfg_attr(not(any(feature = "clans", feature = "leagues", feature = "locations", feature = "labels")), allow(dead_code))]
+    pub(crate) fn new(rest_manager: &RestManager, url: String, parameters: HashMap<String, String>) -> Self {
+        Self {
+            rest_manager: rest_manager.clone(),
+            url,
+            parameters,
+            page_size: None,
+            max_items: None,
+            direction: PaginationDirection::default(),
+            items: VecDeque::new(),
+            page_cursor: None,
+            page_consumed: 0,
+            next_cursor: None,
+            pending_skip: 0,
+            exhausted: false,
+            failed: false,
+            items_seen: 0,
+        }
+    }
+
+    /// Sets the number of items requested per page; the API default is used otherwise.
+    pub fn page_size(mut self, page_size: u32) -> Self {
+        self.page_size = Some(page_size);
+        self
+    }
+
+    /// Stops the iteration once the given number of items has been seen, including items seen before a checkpoint.
+    pub fn max_items(mut self, max_items: usize) -> Self {
+        self.max_items = Some(max_items);
+        self
+    }
+
+    /// Sets the direction the cursors are followed in.
+    ///
+    /// Backward iteration returns the items of every page in reverse order, so the whole sequence is reversed.
+    /// It needs a starting cursor, see `Paginator::start_at`.
+    pub fn direction(mut self, direction: PaginationDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Starts the iteration at the cursor, e.g. `paging.cursors.before` of a page retrieved earlier.
+    pub fn start_at(mut self, cursor: impl AsRef<str>) -> Self {
+        self.next_cursor = Some(cursor.as_ref().to_string());
+        self
+    }
+
+    /// Continues the iteration from a checkpoint saved with `Paginator::checkpoint`.
+    ///
+    /// # Arguments
+    /// * `checkpoint` - The checkpoint of a paginator of the same endpoint.
+    ///
+    /// # Returns
+    /// `Result` containing the `Paginator` positioned at the checkpoint if successful;
+    /// if the checkpoint belongs to another endpoint or query, it contains an `Error`.
+    pub fn resume(mut self, checkpoint: PaginationCheckpoint) -> Result<Self> {
+        if checkpoint.endpoint != self.url {
+            return Err(Error::InvalidCheckpoint(format!(
+                "Checkpoint of {} cannot be used for {}",
+                checkpoint.endpoint, self.url
+            )));
+        }
+        if checkpoint.parameters != self.parameters {
+            return Err(Error::InvalidCheckpoint(format!("Checkpoint of {} was saved for other query parameters", checkpoint.endpoint)));
+        }
+        self.direction = checkpoint.direction;
+        self.next_cursor = checkpoint.cursor;
+        self.pending_skip = checkpoint.skip;
+        self.items_seen = checkpoint.items_seen;
+        self.exhausted = checkpoint.exhausted;
+        Ok(self)
+    }
+
+    /// Returns the current position of the iteration, which can be serialized and passed to `Paginator::resume`.
+    pub fn checkpoint(&self) -> PaginationCheckpoint {
+        let (cursor, skip) = if self.items.is_empty() {
+            (self.next_cursor.clone(), self.pending_skip)
+        } else {
+            (self.page_cursor.clone(), self.page_consumed)
+        };
+        PaginationCheckpoint {
+            endpoint: self.url.clone(),
+            parameters: self.parameters.clone(),
+            direction: self.direction,
+            cursor,
+            skip,
+            items_seen: self.items_seen,
+            exhausted: self.exhausted && self.items.is_empty(),
+        }
+    }
+
+    /// Returns the next item, requesting the next page if needed.
+    ///
+    /// # Returns
+    /// `None` once all items were returned, the iteration stops after the first error.
+    pub async fn next(&mut self) -> Option<Result<T>> {
+        loop {
+            if self.max_items.is_some_and(|max_items| self.items_seen >= max_items) {
+                return None;
+            }
+            if let Some(item) = self.items.pop_front() {
+                self.items_seen += 1;
+                self.page_consumed += 1;
+                return Some(Ok(item));
+            }
+            if self.failed || self.exhausted {
+                return None;
+            }
+            if let Err(err) = self.fetch_next_page().await {
+                self.failed = true;
+                return Some(Err(err));
+            }
+        }
+    }
+
+    /// Returns a stream of all items; pages are requested only when the previous one is consumed.
+    ///
+    /// The stream ends after the first error.
+    pub fn into_stream(self) -> impl Stream<Item = Result<T>> + Send + 'static {
+        futures::stream::unfold(self, |mut paginator| async move {
+            let item = paginator.next().await?;
+            Some((item, paginator))
+        })
+    }
+
+    /// Retrieves all remaining items.
+    ///
+    /// # Returns
+    /// `Result` containing a `Vec<T>` of all items if successful; if an error occurs, it contains an `Error`.
+    pub async fn collect_all(mut self) -> Result<Vec<T>> {
+        let mut items = Vec::new();
+        while let Some(item) = self.next().await {
+            items.push(item?);
+        }
+        Ok(items)
+    }
+
+    async fn fetch_next_page(&mut self) -> Result<()> {
+        let cursor = self.next_cursor.clone();
+        let pagination_options = PaginationOptions::for_direction(self.direction, self.page_size, cursor.clone());
+        let mut parameters = self.parameters.clone();
+        parameters.extend(pagination_options.to_query_parameters());
+        let page: APIPagedResponse<T> = self.rest_manager.get(&self.url, parameters.into()).await?;
+
+        // items skipped after resuming were already returned before the checkpoint
+        let skip = std::mem::take(&mut self.pending_skip);
+        let cursors = page.paging.cursors;
+        let mut items: VecDeque<_> = page.items.into();
+        if self.direction == PaginationDirection::Backward {
+            items.make_contiguous().reverse();
+        }
+        // an empty page with a cursor would otherwise be requested forever
+        self.next_cursor = match self.direction {
+            _ if items.is_empty() => None,
+            PaginationDirection::Forward => cursors.after,
+            PaginationDirection::Backward => cursors.before,
+        };
+        self.exhausted = self.next_cursor.is_none();
+        self.page_consumed = skip.min(items.len());
+        items.drain(..self.page_consumed);
+        self.page_cursor = cursor;
+        self.items = items;
+        Ok(())
+    }
+}
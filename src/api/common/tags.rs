@@ -0,0 +1,159 @@
+use crate::api::common::utils::{id_to_tag, tag_to_id, tag_to_u64, u64_to_tag};
+use crate::errors::{Error, Result};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+/// Characters tags are made of, in the order of their values.
+pub const TAG_ALPHABET: &str = "0289PYLQGRJCUV";
+
+/// Uppercases the tag, prepends `#` and replaces `O` with `0`, which are often mixed up.
+pub(crate) fn clean_tag(tag: &str) -> String {
+    let cleaned: String = tag.trim().trim_start_matches('#').to_uppercase().chars().map(|c| if c == 'O' { '0' } else { c }).collect();
+    format!("#{cleaned}")
+}
+
+/// Cleans the tag like `clean_tag` and checks that it only consists of `TAG_ALPHABET`.
+fn parse_tag(tag: &str) -> Result<String> {
+    let cleaned = clean_tag(tag);
+    let body = &cleaned[1..];
+    if body.is_empty() || !body.chars().all(|c| TAG_ALPHABET.contains(c)) {
+        return Err(Error::InvalidTag(tag.to_string()));
+    }
+    Ok(cleaned)
+}
+
+macro_rules! define_tag {
+    ($(#[$meta:meta])* $name:ident) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $name(String);
+
+        impl $name {
+            /// Parses the tag, with or without `#`, in any case.
+            ///
+            /// # Arguments
+            /// * `tag` - The tag, e.g. `#2PP` or `2pp`.
+            ///
+            /// # Returns
+            /// `Result` containing the tag if successful; if it contains characters that are not used in tags, it contains an `Error`.
+            pub fn parse(tag: impl AsRef<str>) -> Result<Self> {
+                parse_tag(tag.as_ref()).map(Self)
+            }
+
+            /// Checks whether the tag only consists of `TAG_ALPHABET`.
+            ///
+            /// Tags are always valid unless they were deserialized from a tag that does not parse, which is kept as is.
+            pub fn is_valid(&self) -> bool {
+                parse_tag(&self.0).is_ok_and(|tag| tag == self.0)
+            }
+
+            /// Returns the tag with `#`, e.g. `#2PP`.
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+
+            /// Returns the tag encoded for use in URLs, e.g. `%232PP`.
+            pub fn encoded(&self) -> String {
+                urlencoding::encode(&self.0).into_owned()
+            }
+
+            /// Creates the tag from the `(high, low)` pair of its numeric ID, see `id_to_tag`.
+            pub fn from_id(high: u32, low: u32) -> Result<Self> {
+                id_to_tag(high, low).map(Self)
+            }
+
+            /// Returns the `(high, low)` pair of the numeric ID, see `tag_to_id`.
+            pub fn to_id(&self) -> Result<(u32, u32)> {
+                tag_to_id(&self.0)
+            }
+
+            /// Creates the tag from its numeric ID, see `u64_to_tag`.
+            pub fn from_u64(id: u64) -> Result<Self> {
+                u64_to_tag(id).map(Self)
+            }
+
+            /// Returns the numeric ID, see `tag_to_u64`.
+            pub fn to_u64(&self) -> Result<u64> {
+                tag_to_u64(&self.0)
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = Error;
+
+            fn from_str(tag: &str) -> Result<Self> {
+                Self::parse(tag)
+            }
+        }
+
+        impl TryFrom<&str> for $name {
+            type Error = Error;
+
+            fn try_from(tag: &str) -> Result<Self> {
+                Self::parse(tag)
+            }
+        }
+
+        impl TryFrom<String> for $name {
+            type Error = Error;
+
+            fn try_from(tag: String) -> Result<Self> {
+                Self::parse(tag)
+            }
+        }
+
+        impl PartialEq<str> for $name {
+            fn eq(&self, other: &str) -> bool {
+                self.0 == other
+            }
+        }
+
+        impl PartialEq<&str> for $name {
+            fn eq(&self, other: &&str) -> bool {
+                self.0 == *other
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+                serializer.serialize_str(&self.0)
+            }
+        }
+
+        // a single unexpected tag must not fail the whole response, so tags that do not parse are kept as sent
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+                let tag = String::deserialize(deserializer)?;
+                Ok(parse_tag(&tag).map_or(Self(tag), Self))
+            }
+        }
+    };
+}
+
+define_tag!(
+    /// Tag of a player, e.g. `#2PP`.
+    PlayerTag
+);
+
+define_tag!(
+    /// Tag of a clan, e.g. `#2PP`.
+    ClanTag
+);
+
+define_tag!(
+    /// Tag of a clan war league war, e.g. `#8QU8J9LP`; `#0` stands for a war that is not scheduled yet.
+    WarTag
+);
@@ -0,0 +1,86 @@
+use crate::errors::Result;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+/// Format of the times returned by the API, e.g. `20250101T080000.000Z`.
+#[cfg(feature = "chrono")]
+const TIMESTAMP_FORMAT: &str = "%Y%m%dT%H%M%S%.3fZ";
+
+/// Time returned by the API, e.g. `20250101T080000.000Z`.
+///
+/// The raw value is always kept; with the `chrono` feature it is also parsed into a UTC `DateTime`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct APITimestamp {
+    // the format sorts chronologically
+    raw: String,
+    #[cfg(feature = "chrono")]
+    datetime: chrono::DateTime<chrono::Utc>,
+}
+
+impl APITimestamp {
+    /// Creates the timestamp from the value returned by the API.
+    ///
+    /// # Arguments
+    /// * `raw` - The time in the format of the API, e.g. `20250101T080000.000Z`.
+    ///
+    /// # Returns
+    /// `Result` containing the `APITimestamp` if successful; if the time cannot be parsed, it contains an `Error`.
+    pub fn parse(raw: impl AsRef<str>) -> Result<Self> {
+        let raw = raw.as_ref().to_string();
+        #[cfg(feature = "chrono")]
+        {
+            let datetime = chrono::NaiveDateTime::parse_from_str(&raw, TIMESTAMP_FORMAT)
+                .map_err(|_| crate::errors::Error::InvalidTimestamp(raw.clone()))?
+                .and_utc();
+            Ok(Self { raw, datetime })
+        }
+        #[cfg(not(feature = "chrono"))]
+        Ok(Self { raw })
+    }
+
+    /// Returns the time as it was returned by the API.
+    pub fn as_str(&self) -> &str {
+        &self.raw
+    }
+
+    /// Returns the time in UTC.
+    #[cfg(feature = "chrono")]
+    pub fn datetime(&self) -> chrono::DateTime<chrono::Utc> {
+        self.datetime
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<chrono::DateTime<chrono::Utc>> for APITimestamp {
+    fn from(datetime: chrono::DateTime<chrono::Utc>) -> Self {
+        Self {
+            raw: datetime.format(TIMESTAMP_FORMAT).to_string(),
+            datetime,
+        }
+    }
+}
+
+impl fmt::Display for APITimestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.raw)
+    }
+}
+
+impl AsRef<str> for APITimestamp {
+    fn as_ref(&self) -> &str {
+        &self.raw
+    }
+}
+
+impl Serialize for APITimestamp {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.raw)
+    }
+}
+
+impl<'de> Deserialize<'de> for APITimestamp {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+        Self::parse(raw).map_err(serde::de::Error::custom)
+    }
+}
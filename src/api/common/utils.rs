@@ -1,7 +1,74 @@
+use crate::api::common::tags::{clean_tag, TAG_ALPHABET};
+use crate::errors::{Error, Result};
+
+/// Number of characters in `TAG_ALPHABET`, tags are numbers in this base.
+const TAG_BASE: u64 = 14;
+
 /// Normalize a clash of clans tag for API usage
 ///
-/// Removes # prefix and validates format
+/// Prepends # if missing, uppercases the tag, replaces O with 0 and URL-encodes it.
+/// The tag is not validated, use `PlayerTag`, `ClanTag` or `WarTag` for that.
+///
+/// Replacing O with 0 was added together with the tag types, so `normalize_tag("#2PO")` now returns `%232P0`
+/// instead of `%232PO`, matching what the API expects.
 pub fn normalize_tag(tag: &str) -> String {
-    let cleaned = format!("#{}", tag.trim().trim_start_matches('#').to_uppercase());
-    urlencoding::encode(&cleaned).to_string()
-}
\ No newline at end of file
+    urlencoding::encode(&clean_tag(tag)).to_string()
+}
+
+/// Converts a tag to the `(high, low)` pair of the numeric ID it encodes.
+///
+/// # Arguments
+/// * `tag` - The tag, with or without `#`.
+///
+/// # Returns
+/// `Result` containing the `(high, low)` pair if successful; if the tag is invalid or too long, it contains an `Error`.
+pub fn tag_to_id(tag: &str) -> Result<(u32, u32)> {
+    let cleaned = clean_tag(tag);
+    let digits = &cleaned[1..];
+    if digits.is_empty() {
+        return Err(Error::InvalidTag(tag.to_string()));
+    }
+    let total = digits.chars().try_fold(0u64, |total, c| {
+        let digit = TAG_ALPHABET.find(c)? as u64;
+        total.checked_mul(TAG_BASE)?.checked_add(digit)
+    });
+    // the low part of the ID has 32 bits, the high part 8 bits
+    match total.filter(|total| total >> 40 == 0) {
+        Some(total) => Ok(((total & 0xFF) as u32, (total >> 8) as u32)),
+        None => Err(Error::InvalidTag(tag.to_string())),
+    }
+}
+
+/// Converts the `(high, low)` pair of a numeric ID to the tag, e.g. `#2PP`.
+///
+/// # Returns
+/// `Result` containing the tag if successful; if `high` is greater than `255`, it contains an `Error`.
+pub fn id_to_tag(high: u32, low: u32) -> Result<String> {
+    if high > 0xFF {
+        return Err(Error::InvalidTag(format!("({high}, {low})")));
+    }
+    let mut total = ((low as u64) << 8) | high as u64;
+    let alphabet = TAG_ALPHABET.as_bytes();
+    let mut digits = Vec::new();
+    loop {
+        digits.push(alphabet[(total % TAG_BASE) as usize]);
+        total /= TAG_BASE;
+        if total == 0 {
+            break;
+        }
+    }
+    digits.reverse();
+    Ok(format!("#{}", String::from_utf8(digits).expect("Tag alphabet should be ASCII")))
+}
+
+/// Converts a tag to its numeric ID, `high` in the upper and `low` in the lower 32 bits.
+///
+/// IDs of tags sort by `high` first, which keeps them evenly spread across ranges for sharding.
+pub fn tag_to_u64(tag: &str) -> Result<u64> {
+    tag_to_id(tag).map(|(high, low)| ((high as u64) << 32) | low as u64)
+}
+
+/// Converts a numeric ID created by `tag_to_u64` back to the tag.
+pub fn u64_to_tag(id: u64) -> Result<String> {
+    id_to_tag((id >> 32) as u32, id as u32)
+}
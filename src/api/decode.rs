@@ -0,0 +1,362 @@
+use serde::de::value::{BorrowedStrDeserializer, MapDeserializer, SeqDeserializer};
+use serde::de::{self, DeserializeOwned, DeserializeSeed, Deserializer, IntoDeserializer, MapAccess, Visitor};
+use serde::forward_to_deserialize_any;
+use serde_json::{Map, Value};
+use std::cell::RefCell;
+use std::collections::BTreeSet;
+use std::fmt;
+
+/// How strictly responses have to match the models they are decoded into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DecodeMode {
+    /// Responses missing a field of the model fail, fields the model does not know are ignored.
+    #[default]
+    Standard,
+    /// Missing collections and objects are defaulted; missing and unknown fields are returned
+    /// in `Response::diagnostics`, see `RestManager::get_raw`.
+    Lenient,
+    /// Responses missing a field of the model or containing fields it does not know fail.
+    Strict,
+}
+
+/// Differences between a response of an endpoint and its model.
+///
+/// Fields are written as paths with `[]` for array elements, e.g. `memberList[].labels`.
+/// Only collected in `DecodeMode::Lenient` and `DecodeMode::Strict`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct DecodeDiagnostics {
+    /// Endpoint with placeholders, e.g. `clans/{tag}/members`.
+    pub endpoint: String,
+    /// Fields of the model that were absent in the response and got default values.
+    pub missing_fields: BTreeSet<String>,
+    /// Fields of the response the model does not know, their values are dropped.
+    pub unknown_fields: BTreeSet<String>,
+}
+
+impl DecodeDiagnostics {
+    pub(crate) fn new(endpoint: impl AsRef<str>) -> Self {
+        Self {
+            endpoint: endpoint.as_ref().to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// Checks whether the response matched the model exactly.
+    pub fn is_empty(&self) -> bool {
+        self.missing_fields.is_empty() && self.unknown_fields.is_empty()
+    }
+}
+
+impl fmt::Display for DecodeDiagnostics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let join = |fields: &BTreeSet<String>| fields.iter().map(String::as_str).collect::<Vec<_>>().join(", ");
+        write!(f, "{}: missing fields [{}], unknown fields [{}]", self.endpoint, join(&self.missing_fields), join(&self.unknown_fields))
+    }
+}
+
+/// Deserializes `value`, defaulting missing collections and objects and recording what differs from the model.
+pub(crate) fn decode_tracked<T: DeserializeOwned>(value: &Value, diagnostics: &mut DecodeDiagnostics) -> serde_json::Result<T> {
+    let tracked = RefCell::new(std::mem::take(diagnostics));
+    let result = T::deserialize(Tracked {
+        value,
+        path: String::new(),
+        diagnostics: &tracked,
+    });
+    *diagnostics = tracked.into_inner();
+    result
+}
+
+fn field_path(path: &str, name: &str) -> String {
+    if path.is_empty() { name.to_string() } else { format!("{path}.{name}") }
+}
+
+/// JSON value that records the fields of structs it is decoded into.
+struct Tracked<'a, 'de> {
+    value: &'de Value,
+    path: String,
+    diagnostics: &'a RefCell<DecodeDiagnostics>,
+}
+
+impl<'a, 'de> Tracked<'a, 'de> {
+    fn visit_object<V: Visitor<'de>>(self, object: &'de Map<String, Value>, fields: &[&'static str], visitor: V) -> serde_json::Result<V::Value> {
+        let mut entries: Vec<_> = object.iter().map(|(name, value)| (name.as_str(), Some(value))).collect();
+        // absent fields are passed as missing values, so the model decides how to default them
+        entries.extend(fields.iter().filter(|field| !object.contains_key(**field)).map(|field| (*field, None)));
+        visitor.visit_map(TrackedMap {
+            entries: entries.into_iter(),
+            value: None,
+            path: self.path,
+            diagnostics: self.diagnostics,
+        })
+    }
+}
+
+impl<'de> Deserializer<'de> for Tracked<'_, 'de> {
+    type Error = serde_json::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> serde_json::Result<V::Value> {
+        match self.value {
+            Value::Object(object) => self.visit_object(object, &[], visitor),
+            Value::Array(array) => {
+                let path = format!("{}[]", self.path);
+                let diagnostics = self.diagnostics;
+                let elements = array.iter().map(|value| Tracked {
+                    value,
+                    path: path.clone(),
+                    diagnostics,
+                });
+                let mut seq = SeqDeserializer::new(elements);
+                let value = visitor.visit_seq(&mut seq)?;
+                seq.end()?;
+                Ok(value)
+            }
+            value => value.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> serde_json::Result<V::Value> {
+        match self.value {
+            Value::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> serde_json::Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> serde_json::Result<V::Value> {
+        match self.value {
+            Value::Array(_) => self.deserialize_any(visitor),
+            value => value.deserialize_seq(visitor),
+        }
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> serde_json::Result<V::Value> {
+        match self.value {
+            Value::Object(_) => self.deserialize_any(visitor),
+            value => value.deserialize_map(visitor),
+        }
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(self, name: &'static str, fields: &'static [&'static str], visitor: V) -> serde_json::Result<V::Value> {
+        match self.value {
+            Value::Object(object) => {
+                let unknown = object.keys().filter(|name| !fields.contains(&name.as_str()));
+                let unknown: Vec<_> = unknown.map(|name| field_path(&self.path, name)).collect();
+                self.diagnostics.borrow_mut().unknown_fields.extend(unknown);
+                self.visit_object(object, fields, visitor)
+            }
+            value => value.deserialize_struct(name, fields, visitor),
+        }
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> serde_json::Result<V::Value> {
+        self.value.deserialize_bool(visitor)
+    }
+
+    fn deserialize_i8<V: Visitor<'de>>(self, visitor: V) -> serde_json::Result<V::Value> {
+        self.value.deserialize_i8(visitor)
+    }
+
+    fn deserialize_i16<V: Visitor<'de>>(self, visitor: V) -> serde_json::Result<V::Value> {
+        self.value.deserialize_i16(visitor)
+    }
+
+    fn deserialize_i32<V: Visitor<'de>>(self, visitor: V) -> serde_json::Result<V::Value> {
+        self.value.deserialize_i32(visitor)
+    }
+
+    fn deserialize_i64<V: Visitor<'de>>(self, visitor: V) -> serde_json::Result<V::Value> {
+        self.value.deserialize_i64(visitor)
+    }
+
+    fn deserialize_i128<V: Visitor<'de>>(self, visitor: V) -> serde_json::Result<V::Value> {
+        self.value.deserialize_i128(visitor)
+    }
+
+    fn deserialize_u8<V: Visitor<'de>>(self, visitor: V) -> serde_json::Result<V::Value> {
+        self.value.deserialize_u8(visitor)
+    }
+
+    fn deserialize_u16<V: Visitor<'de>>(self, visitor: V) -> serde_json::Result<V::Value> {
+        self.value.deserialize_u16(visitor)
+    }
+
+    fn deserialize_u32<V: Visitor<'de>>(self, visitor: V) -> serde_json::Result<V::Value> {
+        self.value.deserialize_u32(visitor)
+    }
+
+    fn deserialize_u64<V: Visitor<'de>>(self, visitor: V) -> serde_json::Result<V::Value> {
+        self.value.deserialize_u64(visitor)
+    }
+
+    fn deserialize_u128<V: Visitor<'de>>(self, visitor: V) -> serde_json::Result<V::Value> {
+        self.value.deserialize_u128(visitor)
+    }
+
+    fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> serde_json::Result<V::Value> {
+        self.value.deserialize_f32(visitor)
+    }
+
+    fn deserialize_f64<V: Visitor<'de>>(self, visitor: V) -> serde_json::Result<V::Value> {
+        self.value.deserialize_f64(visitor)
+    }
+
+    fn deserialize_char<V: Visitor<'de>>(self, visitor: V) -> serde_json::Result<V::Value> {
+        self.value.deserialize_char(visitor)
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> serde_json::Result<V::Value> {
+        self.value.deserialize_str(visitor)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> serde_json::Result<V::Value> {
+        self.value.deserialize_string(visitor)
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> serde_json::Result<V::Value> {
+        self.value.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> serde_json::Result<V::Value> {
+        self.value.deserialize_byte_buf(visitor)
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> serde_json::Result<V::Value> {
+        self.value.deserialize_unit(visitor)
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(self, name: &'static str, visitor: V) -> serde_json::Result<V::Value> {
+        self.value.deserialize_unit_struct(name, visitor)
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, len: usize, visitor: V) -> serde_json::Result<V::Value> {
+        self.value.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(self, name: &'static str, len: usize, visitor: V) -> serde_json::Result<V::Value> {
+        self.value.deserialize_tuple_struct(name, len, visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(self, name: &'static str, variants: &'static [&'static str], visitor: V) -> serde_json::Result<V::Value> {
+        self.value.deserialize_enum(name, variants, visitor)
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(self, visitor: V) -> serde_json::Result<V::Value> {
+        self.value.deserialize_identifier(visitor)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> serde_json::Result<V::Value> {
+        visitor.visit_unit()
+    }
+}
+
+impl<'a, 'de> IntoDeserializer<'de, serde_json::Error> for Tracked<'a, 'de> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+/// Fields of an object, followed by the fields of the model absent in it.
+struct TrackedMap<'a, 'de, I> {
+    entries: I,
+    value: Option<(&'de str, Option<&'de Value>)>,
+    path: String,
+    diagnostics: &'a RefCell<DecodeDiagnostics>,
+}
+
+impl<'de, I> MapAccess<'de> for TrackedMap<'_, 'de, I>
+where
+    I: Iterator<Item = (&'de str, Option<&'de Value>)>,
+{
+    type Error = serde_json::Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> serde_json::Result<Option<K::Value>> {
+        match self.entries.next() {
+            Some(entry) => {
+                self.value = Some(entry);
+                seed.deserialize(BorrowedStrDeserializer::new(entry.0)).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> serde_json::Result<V::Value> {
+        let (name, value) = self.value.take().expect("Value should follow its key");
+        let path = field_path(&self.path, name);
+        match value {
+            Some(value) => seed.deserialize(Tracked {
+                value,
+                path,
+                diagnostics: self.diagnostics,
+            }),
+            None => seed.deserialize(Missing {
+                path,
+                diagnostics: self.diagnostics,
+            }),
+        }
+    }
+}
+
+/// Field of the model absent in the response.
+///
+/// Options become None, collections and objects are defaulted and recorded, anything else fails.
+struct Missing<'a> {
+    path: String,
+    diagnostics: &'a RefCell<DecodeDiagnostics>,
+}
+
+impl Missing<'_> {
+    fn error(&self) -> serde_json::Error {
+        de::Error::custom(format_args!("missing field `{}`", self.path))
+    }
+
+    fn record(&self) {
+        self.diagnostics.borrow_mut().missing_fields.insert(self.path.clone());
+    }
+}
+
+impl<'de> Deserializer<'de> for Missing<'_> {
+    type Error = serde_json::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> serde_json::Result<V::Value> {
+        Err(self.error())
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> serde_json::Result<V::Value> {
+        visitor.visit_none()
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> serde_json::Result<V::Value> {
+        self.record();
+        visitor.visit_seq(SeqDeserializer::<_, serde_json::Error>::new(std::iter::empty::<()>()))
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> serde_json::Result<V::Value> {
+        self.record();
+        visitor.visit_map(empty_map())
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(self, _name: &'static str, _fields: &'static [&'static str], visitor: V) -> serde_json::Result<V::Value> {
+        // objects whose fields all have defaults are defaulted, the others fail with the path of the object
+        let value = visitor.visit_map(empty_map()).map_err(|_| self.error())?;
+        self.record();
+        Ok(value)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> serde_json::Result<V::Value> {
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct newtype_struct tuple tuple_struct enum identifier
+    }
+}
+
+fn empty_map() -> MapDeserializer<'static, std::iter::Empty<((), ())>, serde_json::Error> {
+    MapDeserializer::new(std::iter::empty())
+}
@@ -1,4 +1,6 @@
-use serde::Deserialize;
+#[cfg(feature = "http")]
+use crate::api::transport::HttpResponse;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt;
 
 #[derive(Debug, Deserialize)]
@@ -8,15 +10,104 @@ pub struct ApiErrorResponse {
     pub message: Option<String>,
 }
 
+/// Reason of an API error, as reported in the `reason` field of the response.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ApiErrorReason {
+    BadRequest,
+    AccessDenied,
+    NotFound,
+    RequestThrottled,
+    UnknownException,
+    InMaintenance,
+    RequestAborted,
+    // reasons not documented by the API
+    Other(String),
+}
+
+impl ApiErrorReason {
+    /// Returns the reason as it is written by the API, e.g. `requestThrottled`.
+    pub fn as_str(&self) -> &str {
+        match self {
+            ApiErrorReason::BadRequest => "badRequest",
+            ApiErrorReason::AccessDenied => "accessDenied",
+            ApiErrorReason::NotFound => "notFound",
+            ApiErrorReason::RequestThrottled => "requestThrottled",
+            ApiErrorReason::UnknownException => "unknownException",
+            ApiErrorReason::InMaintenance => "inMaintenance",
+            ApiErrorReason::RequestAborted => "requestAborted",
+            ApiErrorReason::Other(reason) => reason,
+        }
+    }
+
+    /// Checks whether the same request may succeed later.
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, ApiErrorReason::RequestThrottled | ApiErrorReason::InMaintenance | ApiErrorReason::RequestAborted)
+    }
+
+    /// Checks whether the reason is caused by the API token.
+    ///
+    /// `RestManager` benches a token that fails with such a reason.
+    pub fn is_token_failure(&self) -> bool {
+        matches!(self, ApiErrorReason::AccessDenied | ApiErrorReason::RequestThrottled)
+    }
+
+    #[cfg(feature = "http")]
+    fn from_status(status_code: reqwest::StatusCode) -> Self {
+        match status_code {
+            reqwest::StatusCode::BAD_REQUEST => ApiErrorReason::BadRequest,
+            reqwest::StatusCode::FORBIDDEN => ApiErrorReason::AccessDenied,
+            reqwest::StatusCode::NOT_FOUND => ApiErrorReason::NotFound,
+            reqwest::StatusCode::TOO_MANY_REQUESTS => ApiErrorReason::RequestThrottled,
+            reqwest::StatusCode::INTERNAL_SERVER_ERROR => ApiErrorReason::UnknownException,
+            reqwest::StatusCode::SERVICE_UNAVAILABLE => ApiErrorReason::InMaintenance,
+            reqwest::StatusCode::GATEWAY_TIMEOUT => ApiErrorReason::RequestAborted,
+            _ => ApiErrorReason::Other(status_code.to_string()),
+        }
+    }
+}
+
+impl From<&str> for ApiErrorReason {
+    fn from(reason: &str) -> Self {
+        match reason {
+            "badRequest" => ApiErrorReason::BadRequest,
+            "accessDenied" => ApiErrorReason::AccessDenied,
+            "notFound" => ApiErrorReason::NotFound,
+            "requestThrottled" => ApiErrorReason::RequestThrottled,
+            "unknownException" => ApiErrorReason::UnknownException,
+            "inMaintenance" => ApiErrorReason::InMaintenance,
+            "requestAborted" => ApiErrorReason::RequestAborted,
+            other => ApiErrorReason::Other(other.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for ApiErrorReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for ApiErrorReason {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for ApiErrorReason {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(|reason| ApiErrorReason::from(reason.as_str()))
+    }
+}
+
 #[derive(Debug)]
 pub struct Error {
     pub status_code: u16,
-    pub reason: String,
+    pub reason: ApiErrorReason,
     pub message: String,
 }
 
 impl Error {
-    pub fn new(status_code: u16, reason: String, message: String) -> Self {
+    pub fn new(status_code: u16, reason: ApiErrorReason, message: String) -> Self {
         Self {
             status_code,
             reason,
@@ -24,24 +115,17 @@ impl Error {
         }
     }
 
-    pub async fn from_response(response: reqwest::Response) -> Self {
-        let status_code = response.status();
-        let data: ApiErrorResponse = response.json().await.unwrap_or(ApiErrorResponse {
+    #[cfg(feature = "http")]
+    pub fn from_response(response: &HttpResponse) -> Self {
+        let status_code = response.status;
+        let data: ApiErrorResponse = serde_json::from_slice(&response.body).unwrap_or(ApiErrorResponse {
             reason: None,
             message: None,
         });
-        let reason = data.reason.unwrap_or_else(|| {
-            match status_code {
-                reqwest::StatusCode::BAD_REQUEST => "badRequest".to_string(),
-                reqwest::StatusCode::FORBIDDEN => "accessDenied".to_string(),
-                reqwest::StatusCode::NOT_FOUND => "notFound".to_string(),
-                reqwest::StatusCode::TOO_MANY_REQUESTS => "requestThrottled".to_string(),
-                reqwest::StatusCode::INTERNAL_SERVER_ERROR => "unknownException".to_string(),
-                reqwest::StatusCode::SERVICE_UNAVAILABLE => "inMaintenance".to_string(),
-                reqwest::StatusCode::GATEWAY_TIMEOUT => "requestAborted".to_string(),
-                _ => format!("Unspecified reason occured. Canonical reason: {}", status_code.canonical_reason().unwrap_or_default()),
-            }
-        });
+        let reason = data
+            .reason
+            .map(|reason| ApiErrorReason::from(reason.as_str()))
+            .unwrap_or_else(|| ApiErrorReason::from_status(status_code));
         let message = data.message.unwrap_or_else(|| {
             match status_code {
                 reqwest::StatusCode::BAD_REQUEST => "Client provided incorrect parameters for the request.".to_string(),
@@ -56,6 +140,27 @@ impl Error {
         });
         Self::new(status_code.as_u16(), reason, message)
     }
+
+    /// Checks whether the same request may succeed later.
+    pub fn is_retryable(&self) -> bool {
+        self.reason.is_retryable()
+    }
+
+    pub fn is_not_found(&self) -> bool {
+        self.reason == ApiErrorReason::NotFound
+    }
+
+    pub fn is_maintenance(&self) -> bool {
+        self.reason == ApiErrorReason::InMaintenance
+    }
+
+    pub fn is_access_denied(&self) -> bool {
+        self.reason == ApiErrorReason::AccessDenied
+    }
+
+    pub fn is_throttled(&self) -> bool {
+        self.reason == ApiErrorReason::RequestThrottled
+    }
 }
 
 impl fmt::Display for Error {
@@ -1,2 +1,3 @@
 pub mod models;
+#[cfg(all(feature = "http", feature = "goldpass"))]
 pub mod requests;
\ No newline at end of file
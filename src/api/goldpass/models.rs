@@ -1,8 +1,25 @@
+use crate::api::common::timestamp::APITimestamp;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct APIGoldPassSeason {
-    pub start_time: String,
-    pub end_time: String,
+    pub start_time: APITimestamp,
+    pub end_time: APITimestamp,
+}
+
+#[cfg(feature = "chrono")]
+impl APIGoldPassSeason {
+    /// Returns the number of full days left in the season at the given time, `0` once it has ended.
+    ///
+    /// # Arguments
+    /// * `now` - The time to count the days from.
+    pub fn days_left_at(&self, now: chrono::DateTime<chrono::Utc>) -> i64 {
+        (self.end_time.datetime() - now).num_days().max(0)
+    }
+
+    /// Returns the number of full days left in the season, see `APIGoldPassSeason::days_left_at`.
+    pub fn days_left(&self) -> i64 {
+        self.days_left_at(chrono::Utc::now())
+    }
 }
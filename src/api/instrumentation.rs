@@ -0,0 +1,166 @@
+//! Tracing spans and metrics emitted for every call made by `RestManager`.
+//!
+//! Spans are only emitted with the `tracing` feature, metrics with the `metrics` feature:
+//! * `clash_forge_requests_total` (counter; `method`, `endpoint`, `status`)
+//! * `clash_forge_request_duration_seconds` (histogram; `method`, `endpoint`)
+//! * `clash_forge_retries_total` (counter; `method`, `endpoint`)
+//! * `clash_forge_throttled_total` (counter; `method`, `endpoint`)
+//! * `clash_forge_cache_hits_total` / `clash_forge_cache_misses_total` (counters; `endpoint`)
+
+use crate::errors::{Error, Result};
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Replaces tags, IDs and seasons in the path with placeholders, e.g. `clans/{tag}/members`.
+///
+/// Keeps the number of distinct span and metric labels bounded.
+pub fn endpoint_template(path: &str) -> String {
+    path.split('/')
+        .map(|segment| {
+            let bytes = segment.as_bytes();
+            if segment.starts_with("%23") || segment.starts_with('#') {
+                "{tag}"
+            } else if !segment.is_empty() && bytes.iter().all(u8::is_ascii_digit) {
+                "{id}"
+            } else if bytes.len() == 7 && bytes[4] == b'-' && bytes.iter().enumerate().all(|(i, b)| i == 4 || b.is_ascii_digit()) {
+                "{season_id}"
+            } else {
+                segment
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[derive(Debug, Default)]
+struct CallState {
+    attempts: u32,
+    throttled: u32,
+    // status of the last attempt
+    status: Option<u16>,
+    cache_hit: Option<bool>,
+}
+
+/// Measurements of a single call, including all of its attempts.
+#[derive(Debug)]
+pub(crate) struct Call {
+    method: &'static str,
+    endpoint: String,
+    started: Instant,
+    state: Mutex<CallState>,
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
+}
+
+impl Call {
+    pub(crate) fn start(method: &'static str, path: &str) -> Self {
+        let endpoint = endpoint_template(path);
+        #[cfg(feature = "tracing")]
+        let span = tracing::info_span!(
+            "clash_forge.request",
+            method,
+            endpoint = %endpoint,
+            status = tracing::field::Empty,
+            latency_ms = tracing::field::Empty,
+            retries = tracing::field::Empty,
+            cache_hit = tracing::field::Empty,
+        );
+        Self {
+            method,
+            endpoint,
+            started: Instant::now(),
+            state: Mutex::default(),
+            #[cfg(feature = "tracing")]
+            span,
+        }
+    }
+
+    /// Runs the future inside the span of the call.
+    #[cfg(feature = "tracing")]
+    pub(crate) fn instrument<F: Future>(&self, future: F) -> impl Future<Output = F::Output> {
+        tracing::Instrument::instrument(future, self.span.clone())
+    }
+
+    /// Runs the future inside the span of the call.
+    #[cfg(not(feature = "tracing"))]
+    pub(crate) fn instrument<F: Future>(&self, future: F) -> F {
+        future
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, CallState> {
+        self.state.lock().expect("Call state lock should not be poisoned")
+    }
+
+    pub(crate) fn attempt(&self) {
+        self.state().attempts += 1;
+    }
+
+    pub(crate) fn status(&self, status: u16) {
+        let mut state = self.state();
+        state.status = Some(status);
+        if status == 429 {
+            state.throttled += 1;
+        }
+    }
+
+    pub(crate) fn cache_hit(&self, cache_hit: bool) {
+        self.state().cache_hit = Some(cache_hit);
+    }
+
+    pub(crate) fn retry(&self, delay: Duration, error: &Error) {
+        #[cfg(feature = "tracing")]
+        tracing::warn!(parent: &self.span, attempt = self.state().attempts, delay_ms = delay.as_millis() as u64, %error, "retrying request");
+        #[cfg(not(feature = "tracing"))]
+        let _ = (delay, error);
+    }
+
+    pub(crate) fn finish<T>(self, result: &Result<T>) {
+        let latency = self.started.elapsed();
+        let CallState { attempts, throttled, status, cache_hit } = std::mem::take(&mut *self.state());
+        let retries = attempts.saturating_sub(1);
+        #[cfg(feature = "tracing")]
+        {
+            if let Some(status) = status {
+                self.span.record("status", status);
+            }
+            if let Some(cache_hit) = cache_hit {
+                self.span.record("cache_hit", cache_hit);
+            }
+            self.span.record("latency_ms", latency.as_millis() as u64);
+            self.span.record("retries", retries);
+            match result {
+                Ok(_) => tracing::debug!(parent: &self.span, "request finished"),
+                Err(error) => tracing::warn!(parent: &self.span, %error, "request failed"),
+            }
+        }
+        #[cfg(feature = "metrics")]
+        {
+            let status = match (status, cache_hit) {
+                (Some(status), _) => status.to_string(),
+                (None, Some(true)) => "cached".to_string(),
+                (None, _) => "error".to_string(),
+            };
+            let labels = [("method", self.method.to_string()), ("endpoint", self.endpoint.clone())];
+            metrics::counter!("clash_forge_requests_total", "method" => self.method, "endpoint" => self.endpoint.clone(), "status" => status).increment(1);
+            metrics::histogram!("clash_forge_request_duration_seconds", &labels).record(latency.as_secs_f64());
+            if retries > 0 {
+                metrics::counter!("clash_forge_retries_total", &labels).increment(retries as u64);
+            }
+            if throttled > 0 {
+                metrics::counter!("clash_forge_throttled_total", &labels).increment(throttled as u64);
+            }
+            match cache_hit {
+                Some(true) => metrics::counter!("clash_forge_cache_hits_total", "endpoint" => self.endpoint.clone()).increment(1),
+                Some(false) => metrics::counter!("clash_forge_cache_misses_total", "endpoint" => self.endpoint.clone()).increment(1),
+                None => {}
+            }
+        }
+        #[cfg(not(feature = "metrics"))]
+        let _ = (self.method, self.endpoint, throttled);
+        #[cfg(not(feature = "tracing"))]
+        let _ = result;
+        #[cfg(not(any(feature = "tracing", feature = "metrics")))]
+        let _ = (status, cache_hit, latency, retries);
+    }
+}
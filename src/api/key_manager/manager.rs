@@ -0,0 +1,213 @@
+use crate::api;
+use crate::api::key_manager::models::{
+    APICreateKeyRequest, APICreateKeyResponse, APIDeveloperKey, APIDeveloperKeyList, APILoginRequest, APILoginResponse,
+    APIRevokeKeyRequest, APITemporaryTokenClaims,
+};
+use crate::api::rest_manager::{RestManager, RestManagerConfig};
+use crate::api::transport::HttpResponse;
+use crate::errors::{Error, Result};
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use reqwest::header::{HeaderMap, HeaderValue, ACCEPT};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// The developer portal does not allow more keys per account.
+const MAX_KEYS_PER_ACCOUNT: usize = 10;
+
+#[derive(Debug, Clone)]
+pub struct KeyManagerConfig {
+    pub timeout: Duration,
+    pub base_url: String,
+    pub user_agent: String,
+    /// Name of the keys managed by `KeyManager`; keys with other names are never touched.
+    pub key_name: String,
+    pub key_description: String,
+    /// Number of keys `KeyManager::ensure_keys` keeps for the current IP.
+    pub key_count: usize,
+}
+
+impl Default for KeyManagerConfig {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(30),
+            base_url: "https://developer.clashofclans.com/api".to_string(),
+            user_agent: format!("clash-forge/{}", env!("CARGO_PKG_VERSION")),
+            key_name: "clash-forge".to_string(),
+            key_description: "Created by clash-forge".to_string(),
+            key_count: 1,
+        }
+    }
+}
+
+/// Client for the developer portal that manages API keys for the IP of this machine.
+#[derive(Debug, Clone)]
+pub struct KeyManager {
+    http_client: reqwest::Client,
+    config: Arc<KeyManagerConfig>,
+    ip: String,
+}
+
+impl KeyManager {
+    /// Logs into the developer portal and detects the IP keys have to be created for.
+    ///
+    /// # Arguments
+    /// * `email` - The email of the developer account.
+    /// * `password` - The password of the developer account.
+    /// * `config` - The configuration of the key manager.
+    ///
+    /// # Returns
+    /// `Result` containing the logged in `KeyManager` if successful; if an error occurs, it contains an `Error`.
+    pub async fn login(email: impl AsRef<str>, password: impl AsRef<str>, config: KeyManagerConfig) -> Result<Self> {
+        let mut headers = HeaderMap::new();
+        headers.insert(ACCEPT, HeaderValue::from_static("application/json"));
+
+        let http_client = reqwest::Client::builder()
+            .timeout(config.timeout)
+            .default_headers(headers)
+            .user_agent(&config.user_agent)
+            .cookie_store(true)
+            .build()?;
+
+        let request = APILoginRequest {
+            email: email.as_ref().to_string(),
+            password: password.as_ref().to_string(),
+        };
+        let url = format!("{}/login", config.base_url);
+        let response = http_client.post(url).json(&request).send().await?;
+        let login: APILoginResponse = get_data(response).await?;
+        let ip = detect_ip(&login.temporary_api_token)?;
+
+        Ok(KeyManager { http_client, config: Arc::new(config), ip })
+    }
+
+    /// Returns the IP detected on login.
+    pub fn ip(&self) -> &str {
+        &self.ip
+    }
+
+    /// Retrieves all keys of the developer account.
+    ///
+    /// # Returns
+    /// `Result` containing a `Vec<APIDeveloperKey>` if successful; if an error occurs, it contains an `Error`.
+    pub async fn keys(&self) -> Result<Vec<APIDeveloperKey>> {
+        self.post("apikey/list", None::<&()>).await.map(|x: APIDeveloperKeyList| x.keys)
+    }
+
+    /// Creates a key whitelisted for the detected IP.
+    ///
+    /// # Returns
+    /// `Result` containing the created `APIDeveloperKey` if successful; if an error occurs, it contains an `Error`.
+    pub async fn create_key(&self) -> Result<APIDeveloperKey> {
+        let request = APICreateKeyRequest {
+            name: self.config.key_name.clone(),
+            description: self.config.key_description.clone(),
+            cidr_ranges: vec![self.ip.clone()],
+            scopes: vec!["clash".to_string()],
+        };
+        self.post("apikey/create", Some(&request)).await.map(|x: APICreateKeyResponse| x.key)
+    }
+
+    /// Revokes a key.
+    ///
+    /// # Arguments
+    /// * `id` - The ID of the key.
+    pub async fn revoke_key(&self, id: impl AsRef<str>) -> Result<()> {
+        let request = APIRevokeKeyRequest { id: id.as_ref().to_string() };
+        self.post("apikey/revoke", Some(&request)).await.map(|_: serde_json::Value| ())
+    }
+
+    /// Revokes managed keys that are not valid for the detected IP and creates new ones
+    /// until `KeyManagerConfig::key_count` keys are available.
+    ///
+    /// # Returns
+    /// `Result` containing the tokens valid for the detected IP if successful; if an error occurs, it contains an `Error`.
+    pub async fn ensure_keys(&self) -> Result<Vec<String>> {
+        let keys = self.keys().await?;
+        let mut total = keys.len();
+
+        let mut tokens = Vec::new();
+        for key in keys.into_iter().filter(|key| key.name == self.config.key_name) {
+            let whitelisted = key.cidr_ranges.as_ref().is_some_and(|ranges| ranges.iter().any(|range| self.matches_ip(range)));
+            if whitelisted && tokens.len() < self.config.key_count {
+                tokens.push(key.key);
+            } else if !whitelisted {
+                self.revoke_key(&key.id).await?;
+                total -= 1;
+            }
+        }
+
+        while tokens.len() < self.config.key_count {
+            if total >= MAX_KEYS_PER_ACCOUNT {
+                return Err(Error::KeyManager(format!(
+                    "Developer account already has {MAX_KEYS_PER_ACCOUNT} keys, {} more needed for {}",
+                    self.config.key_count - tokens.len(),
+                    self.ip,
+                )));
+            }
+            tokens.push(self.create_key().await?.key);
+            total += 1;
+        }
+        Ok(tokens)
+    }
+
+    /// Makes sure keys for the detected IP exist and creates a `RestManager` that uses them.
+    ///
+    /// # Arguments
+    /// * `config` - The configuration of the created `RestManager`.
+    ///
+    /// # Returns
+    /// `Result` containing the `RestManager` if successful; if an error occurs, it contains an `Error`.
+    pub async fn rest_manager(&self, config: RestManagerConfig) -> Result<RestManager> {
+        let tokens = self.ensure_keys().await?;
+        RestManager::with_tokens(tokens, config)
+    }
+
+    fn matches_ip(&self, range: &str) -> bool {
+        range.trim_end_matches("/32") == self.ip
+    }
+
+    async fn post<T, K>(&self, url: &str, json: Option<&T>) -> Result<K>
+    where
+        T: serde::Serialize,
+        K: serde::de::DeserializeOwned,
+    {
+        let url = format!("{}/{}", self.config.base_url, url);
+        let mut request = self.http_client.post(url);
+        if let Some(json) = json {
+            request = request.json(json);
+        }
+        get_data(request.send().await?).await
+    }
+}
+
+async fn get_data<T>(response: reqwest::Response) -> Result<T>
+where
+    T: serde::de::DeserializeOwned,
+{
+    let response = HttpResponse::from_reqwest(response).await?;
+    if response.status.is_success() {
+        let data = serde_json::from_slice(&response.body)?;
+        Ok(data)
+    } else {
+        Err(Error::Api(api::errors::Error::from_response(&response)))
+    }
+}
+
+/// Extracts the IP of this machine from the temporary token the portal issues on login.
+fn detect_ip(temporary_token: &str) -> Result<String> {
+    let payload = temporary_token
+        .split('.')
+        .nth(1)
+        .and_then(|payload| URL_SAFE_NO_PAD.decode(payload.trim_end_matches('=')).ok())
+        .ok_or_else(|| Error::KeyManager("Temporary token is not a valid JWT".to_string()))?;
+    let claims: APITemporaryTokenClaims = serde_json::from_slice(&payload)?;
+    claims
+        .limits
+        .into_iter()
+        .filter(|limit| limit.r#type == "client")
+        .flat_map(|limit| limit.cidrs.unwrap_or_default())
+        .next()
+        .map(|cidr| cidr.trim_end_matches("/32").to_string())
+        .ok_or_else(|| Error::KeyManager("Temporary token does not contain the client IP".to_string()))
+}
@@ -0,0 +1,81 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+// ---------- Login ----------
+
+#[derive(Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct APILoginRequest {
+    pub email: String,
+    pub password: String,
+}
+
+// keeps the password out of logs
+impl fmt::Debug for APILoginRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("APILoginRequest")
+            .field("email", &self.email)
+            .field("password", &"<redacted>")
+            .finish()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct APILoginResponse {
+    #[serde(rename = "temporaryAPIToken")]
+    pub temporary_api_token: String,
+    pub session_expires_in_seconds: Option<i64>,
+}
+
+// ---------- API Keys ----------
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct APIDeveloperKey {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    // keys without IP restrictions do not have ranges
+    pub cidr_ranges: Option<Vec<String>>,
+    pub scopes: Option<Vec<String>>,
+    pub key: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct APIDeveloperKeyList {
+    pub keys: Vec<APIDeveloperKey>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct APICreateKeyRequest {
+    pub name: String,
+    pub description: String,
+    pub cidr_ranges: Vec<String>,
+    pub scopes: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct APICreateKeyResponse {
+    pub key: APIDeveloperKey,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct APIRevokeKeyRequest {
+    pub id: String,
+}
+
+// ---------- Temporary Token ----------
+
+/// Payload of the temporary token returned on login, only used to detect the IP.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct APITemporaryTokenClaims {
+    pub limits: Vec<APITemporaryTokenLimit>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct APITemporaryTokenLimit {
+    pub r#type: String,
+    // only for "client" limits
+    pub cidrs: Option<Vec<String>>,
+}
@@ -0,0 +1,178 @@
+use crate::api::rate_limiter::{RateLimit, RateLimiter};
+use crate::errors::{Error, Result};
+use reqwest::header::HeaderValue;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Strategy used to pick the API token for the next request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum KeySelection {
+    /// Tokens are used one after another.
+    #[default]
+    RoundRobin,
+    /// The token that has been idle the longest is used.
+    LeastRecentlyUsed,
+}
+
+/// Snapshot of the state of a single API token in the pool.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyHealth {
+    /// Position of the token in the list it was created from.
+    pub index: usize,
+    /// Last characters of the token, safe to show in logs.
+    pub token_hint: String,
+    pub requests: u64,
+    pub failures: u64,
+    pub last_used: Option<Instant>,
+    // None if the token is not benched
+    pub benched_until: Option<Instant>,
+}
+
+impl KeyHealth {
+    /// Checks whether the token can currently be used.
+    pub fn is_available(&self) -> bool {
+        self.benched_until.is_none_or(|until| until <= Instant::now())
+    }
+}
+
+#[derive(Debug, Default)]
+struct KeyState {
+    requests: u64,
+    failures: u64,
+    last_used: Option<Instant>,
+    benched_until: Option<Instant>,
+}
+
+impl KeyState {
+    fn is_available(&self, now: Instant) -> bool {
+        self.benched_until.is_none_or(|until| until <= now)
+    }
+}
+
+#[derive(Debug)]
+struct ApiKey {
+    authorization: HeaderValue,
+    token_hint: String,
+    rate_limiter: Option<RateLimiter>,
+    state: Mutex<KeyState>,
+}
+
+/// Token chosen for a single request.
+#[derive(Debug, Clone)]
+pub(crate) struct SelectedKey {
+    key: Arc<ApiKey>,
+}
+
+impl SelectedKey {
+    pub(crate) fn authorization(&self) -> HeaderValue {
+        self.key.authorization.clone()
+    }
+
+    /// Waits for the request budget of the token.
+    pub(crate) async fn acquire(&self) {
+        if let Some(rate_limiter) = &self.key.rate_limiter {
+            rate_limiter.acquire().await;
+        }
+    }
+}
+
+/// Set of API tokens the requests are distributed across.
+#[derive(Debug)]
+pub(crate) struct KeyPool {
+    keys: Vec<Arc<ApiKey>>,
+    selection: KeySelection,
+    bench_duration: Duration,
+    next: AtomicUsize,
+}
+
+impl KeyPool {
+    pub(crate) fn new<I, S>(tokens: I, selection: KeySelection, bench_duration: Duration, rate_limit: Option<RateLimit>) -> Result<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let keys = tokens
+            .into_iter()
+            .map(|token| {
+                let token = token.as_ref();
+                let mut authorization = HeaderValue::from_str(&format!("Bearer {token}")).map_err(|_| Error::InvalidToken)?;
+                authorization.set_sensitive(true);
+                let hint_start = token.char_indices().rev().nth(3).map_or(0, |(index, _)| index);
+                Ok(Arc::new(ApiKey {
+                    authorization,
+                    token_hint: format!("...{}", &token[hint_start..]),
+                    rate_limiter: rate_limit.map(RateLimiter::new),
+                    state: Mutex::new(KeyState::default()),
+                }))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if keys.is_empty() {
+            return Err(Error::InvalidToken);
+        }
+        Ok(Self {
+            keys,
+            selection,
+            bench_duration,
+            next: AtomicUsize::new(0),
+        })
+    }
+
+    /// Picks the token for the next request.
+    ///
+    /// If every token is benched, the one that recovers first is used.
+    pub(crate) fn select(&self) -> SelectedKey {
+        let now = Instant::now();
+        let states: Vec<_> = self.keys.iter().map(|key| key.state.lock().expect("Key state lock should not be poisoned")).collect();
+        let available = |index: &usize| states[*index].is_available(now);
+        let chosen = match self.selection {
+            KeySelection::RoundRobin => {
+                let start = self.next.fetch_add(1, Ordering::Relaxed);
+                (0..self.keys.len()).map(|offset| (start + offset) % self.keys.len()).find(available)
+            }
+            KeySelection::LeastRecentlyUsed => (0..self.keys.len()).filter(available).min_by_key(|index| states[*index].last_used),
+        };
+        let index = chosen.unwrap_or_else(|| {
+            (0..self.keys.len()).min_by_key(|index| states[*index].benched_until).expect("Key pool is never empty")
+        });
+        drop(states);
+
+        let key = &self.keys[index];
+        let mut state = key.state.lock().expect("Key state lock should not be poisoned");
+        state.requests += 1;
+        state.last_used = Some(now);
+        SelectedKey { key: key.clone() }
+    }
+
+    /// Takes the token out of rotation for the bench duration.
+    pub(crate) fn bench(&self, selected: &SelectedKey) {
+        let mut state = selected.key.state.lock().expect("Key state lock should not be poisoned");
+        state.failures += 1;
+        state.benched_until = Some(Instant::now() + self.bench_duration);
+    }
+
+    /// Checks whether any token can currently be used.
+    pub(crate) fn has_available(&self) -> bool {
+        let now = Instant::now();
+        self.keys.iter().any(|key| key.state.lock().expect("Key state lock should not be poisoned").is_available(now))
+    }
+
+    pub(crate) fn health(&self) -> Vec<KeyHealth> {
+        let now = Instant::now();
+        self.keys
+            .iter()
+            .enumerate()
+            .map(|(index, key)| {
+                let state = key.state.lock().expect("Key state lock should not be poisoned");
+                KeyHealth {
+                    index,
+                    token_hint: key.token_hint.clone(),
+                    requests: state.requests,
+                    failures: state.failures,
+                    last_used: state.last_used,
+                    benched_until: state.benched_until.filter(|until| *until > now),
+                }
+            })
+            .collect()
+    }
+}
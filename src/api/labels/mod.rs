@@ -1,2 +1,3 @@
 pub mod models;
+#[cfg(all(feature = "http", feature = "labels"))]
 pub mod requests;
\ No newline at end of file
@@ -1,8 +1,10 @@
 use crate::api::common::models::APIPagedResponse;
 use crate::api::common::pagination::PaginationOptions;
+use crate::api::common::paginator::Paginator;
 use crate::api::labels::models::APILabel;
 use crate::api::rest_manager::RestManager;
 use crate::errors::Result;
+use std::collections::HashMap;
 
 macro_rules! format_url {
     ($fmt:expr, $($arg:expr)*) => {
@@ -24,6 +26,14 @@ impl RestManager {
         self.get(&url, pagination_options.to_query_parameters().into()).await
     }
 
+    /// Iterates over all player labels.
+    ///
+    /// # Returns
+    /// `Paginator<APILabel>` that requests the pages lazily.
+    pub fn player_labels_paginated(&self) -> Paginator<APILabel> {
+        Paginator::new(self, format_url!("players",), HashMap::new())
+    }
+
     /// Retrieves a full list of clan labels.
     ///
     /// # Arguments
@@ -35,4 +45,12 @@ impl RestManager {
         let url = format_url!("clans",);
         self.get(&url, pagination_options.to_query_parameters().into()).await
     }
+
+    /// Iterates over all clan labels.
+    ///
+    /// # Returns
+    /// `Paginator<APILabel>` that requests the pages lazily.
+    pub fn clan_labels_paginated(&self) -> Paginator<APILabel> {
+        Paginator::new(self, format_url!("clans",), HashMap::new())
+    }
 }
\ No newline at end of file
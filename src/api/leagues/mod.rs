@@ -1,2 +1,3 @@
 pub mod models;
+#[cfg(all(feature = "http", feature = "leagues"))]
 pub mod requests;
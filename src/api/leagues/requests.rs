@@ -1,9 +1,11 @@
 use crate::api::common::models::APIPagedResponse;
 use crate::api::common::pagination::PaginationOptions;
+use crate::api::common::paginator::Paginator;
 use crate::api::leagues::models::{APIBuilderBaseLeague, APICapitalLeague, APILeague, APILeagueSeason, APIWarLeague};
 use crate::api::locations::models::APIPlayerRanking;
 use crate::api::rest_manager::RestManager;
 use crate::errors::Result;
+use std::collections::HashMap;
 
 macro_rules! format_url {
     ($r#type:expr, $fmt:expr, $($arg:expr),*) => {
@@ -87,6 +89,19 @@ impl RestManager {
         self.get(&url, pagination_options.to_query_parameters().into()).await
     }
 
+    /// Iterates over all player rankings of a league season.
+    ///
+    /// # Arguments
+    /// * `league_id` - The ID of the league.
+    /// * `season_id` - The ID of the season.
+    ///
+    /// # Returns
+    /// `Paginator<APIPlayerRanking>` that requests the pages lazily.
+    pub fn league_season_rankings_paginated(&self, league_id: impl AsRef<str>, season_id: impl AsRef<str>) -> Paginator<APIPlayerRanking> {
+        let url = format_url!("", "{}/seasons/{}", league_id.as_ref(), season_id.as_ref());
+        Paginator::new(self, url, HashMap::new())
+    }
+
     /// Retrieves a full list of war leagues.
     ///
     /// # Arguments
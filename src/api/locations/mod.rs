@@ -1,2 +1,3 @@
 pub mod models;
+#[cfg(all(feature = "http", feature = "locations"))]
 pub mod requests;
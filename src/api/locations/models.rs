@@ -1,4 +1,5 @@
 use crate::api::common::models::APIBadge;
+use crate::api::common::tags::{ClanTag, PlayerTag};
 use crate::api::leagues::models::{APIBuilderBaseLeague, APILeague};
 use serde::{Deserialize, Serialize};
 
@@ -20,7 +21,7 @@ pub struct APILocation {
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct APIPlayerRanking {
-    pub tag: String,
+    pub tag: PlayerTag,
     pub name: String,
     pub exp_level: i64,
     pub trophies: i64,
@@ -35,7 +36,7 @@ pub struct APIPlayerRanking {
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct APIPlayerRankingClan {
-    pub tag: String,
+    pub tag: ClanTag,
     pub name: String,
     pub badge_urls: APIBadge
 }
@@ -43,7 +44,7 @@ pub struct APIPlayerRankingClan {
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct APIClanRanking {
-    pub tag: String,
+    pub tag: ClanTag,
     pub name: String,
     pub location: APILocation,
     pub badge_urls: APIBadge,
@@ -57,7 +58,7 @@ pub struct APIClanRanking {
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct APIPlayerBuilderBaseRanking {
-    pub tag: String,
+    pub tag: PlayerTag,
     pub name: String,
     pub exp_level: i64,
     pub rank: i64,
@@ -70,7 +71,7 @@ pub struct APIPlayerBuilderBaseRanking {
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct APIClanBuilderBaseRanking {
-    pub tag: String,
+    pub tag: ClanTag,
     pub name: String,
     pub location: APILocation,
     pub badge_urls: APIBadge,
@@ -84,7 +85,7 @@ pub struct APIClanBuilderBaseRanking {
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct APIClanCapitalRanking {
-    pub tag: String,
+    pub tag: ClanTag,
     pub name: String,
     pub location: APILocation,
     pub badge_urls: APIBadge,
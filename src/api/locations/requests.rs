@@ -1,8 +1,10 @@
 use crate::api::common::models::APIPagedResponse;
 use crate::api::common::pagination::PaginationOptions;
+use crate::api::common::paginator::Paginator;
 use crate::api::locations::models::{APIClanBuilderBaseRanking, APIClanCapitalRanking, APIClanRanking, APILocation, APIPlayerBuilderBaseRanking, APIPlayerRanking};
 use crate::api::rest_manager::RestManager;
 use crate::errors::Result;
+use std::collections::HashMap;
 
 macro_rules! format_url {
     ($fmt:expr, $($arg:expr)*) => {
@@ -48,6 +50,17 @@ impl RestManager {
         self.get(&url, pagination_options.to_query_parameters().into()).await
     }
 
+    /// Iterates over all player rankings for a specific location.
+    ///
+    /// # Arguments
+    /// * `location_id` - The ID of the location.
+    ///
+    /// # Returns
+    /// `Paginator<APIPlayerRanking>` that requests the pages lazily.
+    pub fn players_rankings_paginated(&self, location_id: impl AsRef<str>) -> Paginator<APIPlayerRanking> {
+        Paginator::new(self, format_url!("{}/rankings/players", location_id.as_ref()), HashMap::new())
+    }
+
     /// Retrieves clan rankings for a specific location.
     ///
     /// # Arguments
@@ -60,6 +73,17 @@ impl RestManager {
         self.get(&url, pagination_options.to_query_parameters().into()).await
     }
 
+    /// Iterates over all clan rankings for a specific location.
+    ///
+    /// # Arguments
+    /// * `location_id` - The ID of the location.
+    ///
+    /// # Returns
+    /// `Paginator<APIClanRanking>` that requests the pages lazily.
+    pub fn clans_rankings_paginated(&self, location_id: impl AsRef<str>) -> Paginator<APIClanRanking> {
+        Paginator::new(self, format_url!("{}/rankings/clans", location_id.as_ref()), HashMap::new())
+    }
+
     /// Retrieves player builder base rankings for a specific location.
     ///
     /// # Arguments
@@ -73,6 +97,17 @@ impl RestManager {
         self.get(&url, pagination_options.to_query_parameters().into()).await
     }
 
+    /// Iterates over all player builder base rankings for a specific location.
+    ///
+    /// # Arguments
+    /// * `location_id` - The ID of the location.
+    ///
+    /// # Returns
+    /// `Paginator<APIPlayerBuilderBaseRanking>` that requests the pages lazily.
+    pub fn players_builder_base_rankings_paginated(&self, location_id: impl AsRef<str>) -> Paginator<APIPlayerBuilderBaseRanking> {
+        Paginator::new(self, format_url!("{}/rankings/players-builder-base", location_id.as_ref()), HashMap::new())
+    }
+
     /// Retrieves clan builder base rankings for a specific location.
     ///
     /// # Arguments
@@ -86,6 +121,17 @@ impl RestManager {
         self.get(&url, pagination_options.to_query_parameters().into()).await
     }
 
+    /// Iterates over all clan builder base rankings for a specific location.
+    ///
+    /// # Arguments
+    /// * `location_id` - The ID of the location.
+    ///
+    /// # Returns
+    /// `Paginator<APIClanBuilderBaseRanking>` that requests the pages lazily.
+    pub fn clans_builder_base_rankings_paginated(&self, location_id: impl AsRef<str>) -> Paginator<APIClanBuilderBaseRanking> {
+        Paginator::new(self, format_url!("{}/rankings/clans-builder-base", location_id.as_ref()), HashMap::new())
+    }
+
     /// Retrieves clan capital rankings for a specific location.
     ///
     /// # Arguments
@@ -98,4 +144,15 @@ impl RestManager {
         let url = format_url!("{}/rankings/capitals", location_id.as_ref());
         self.get(&url, pagination_options.to_query_parameters().into()).await
     }
+
+    /// Iterates over all clan capital rankings for a specific location.
+    ///
+    /// # Arguments
+    /// * `location_id` - The ID of the location.
+    ///
+    /// # Returns
+    /// `Paginator<APIClanCapitalRanking>` that requests the pages lazily.
+    pub fn capitals_rankings_paginated(&self, location_id: impl AsRef<str>) -> Paginator<APIClanCapitalRanking> {
+        Paginator::new(self, format_url!("{}/rankings/capitals", location_id.as_ref()), HashMap::new())
+    }
 }
\ No newline at end of file
@@ -0,0 +1,24 @@
+use crate::api::transport::{HttpRequest, HttpResponse};
+use std::fmt;
+use std::time::Duration;
+
+/// Hook that observes and alters every request sent by `RestManager`.
+///
+/// Middleware runs for every attempt, after the API token is added to the request.
+/// `before_request` hooks run in the configured order, `after_response` hooks in reverse.
+pub trait Middleware: fmt::Debug + Send + Sync {
+    /// Called before the request is sent.
+    ///
+    /// Returning a response skips the transport and the remaining `before_request` hooks.
+    fn before_request(&self, request: &mut HttpRequest) -> Option<HttpResponse> {
+        let _ = request;
+        None
+    }
+
+    /// Called after the response is received, whatever its status.
+    ///
+    /// Not called if the transport fails without a response.
+    fn after_response(&self, request: &HttpRequest, response: &mut HttpResponse, elapsed: Duration) {
+        let _ = (request, response, elapsed);
+    }
+}
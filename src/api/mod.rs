@@ -1,9 +1,39 @@
+// models of a group are also compiled for the groups whose models refer to them
+#[cfg(any(feature = "players", feature = "clans"))]
 pub mod players;
+#[cfg(any(feature = "clans", feature = "players"))]
 pub mod clans;
+#[cfg(any(feature = "leagues", feature = "locations", feature = "clans", feature = "players"))]
 pub mod leagues;
+#[cfg(feature = "goldpass")]
 pub mod goldpass;
+#[cfg(any(feature = "locations", feature = "leagues", feature = "clans", feature = "players"))]
 pub mod locations;
+#[cfg(any(feature = "labels", feature = "clans", feature = "players"))]
 pub mod labels;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+#[cfg(feature = "http")]
+pub mod cache;
 pub mod common;
+#[cfg(feature = "http")]
+pub mod decode;
+#[cfg(feature = "http")]
+pub mod instrumentation;
+#[cfg(feature = "http")]
+pub mod key_manager;
+#[cfg(feature = "http")]
+pub mod key_pool;
+#[cfg(feature = "http")]
+pub mod middleware;
+#[cfg(feature = "http")]
+pub mod rate_limiter;
+#[cfg(feature = "http")]
+pub mod response;
+#[cfg(feature = "http")]
 pub mod rest_manager;
+#[cfg(feature = "http")]
+pub mod retry;
+#[cfg(feature = "http")]
+pub mod transport;
 pub mod errors;
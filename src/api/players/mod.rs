@@ -1,3 +1,4 @@
 pub mod models;
+#[cfg(all(feature = "http", feature = "players"))]
 pub mod requests;
 
@@ -1,5 +1,7 @@
 use crate::api::clans::models::APIClanMemberRole;
+use crate::api::common::enums::api_enum;
 use crate::api::common::models::APIBadge;
+use crate::api::common::tags::{ClanTag, PlayerTag};
 use crate::api::labels::models::APILabel;
 use crate::api::leagues::models::{APIBuilderBaseLeague, APILeague};
 use serde::{Deserialize, Serialize};
@@ -13,7 +15,7 @@ pub struct APIVerifyTokenRequest {
 
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 pub struct APIVerifyTokenResponse {
-    pub tag: String,
+    pub tag: PlayerTag,
     pub token: String,
     pub status: String,
 }
@@ -23,7 +25,7 @@ pub struct APIVerifyTokenResponse {
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct APIPlayer {
-    pub tag: String,
+    pub tag: PlayerTag,
     pub name: String,
     pub town_hall_level: i64,
     pub town_hall_weapon_level: i64,
@@ -46,7 +48,8 @@ pub struct APIPlayer {
     // if player doesn't have a clan
     pub clan: Option<APIPlayerClan>,
     pub league: APILeague,
-    pub builder_base_league: APIBuilderBaseLeague,
+    // missing in some older or edge-case responses
+    pub builder_base_league: Option<APIBuilderBaseLeague>,
     // if player never was in legend league
     pub legend_statistics: Option<APIPlayerLegendStatistics>,
     pub achievements: Vec<APIPlayerAchievementProgress>,
@@ -59,18 +62,17 @@ pub struct APIPlayer {
     pub spells: Vec<APIPlayerItemLevel>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
-pub enum APIWarPreference {
-    #[serde(rename = "out")]
-    Out,
-    #[serde(rename = "in")]
-    In,
+api_enum! {
+    pub enum APIWarPreference {
+        Out = "out",
+        In = "in",
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct APIPlayerClan {
-    pub tag: String,
+    pub tag: ClanTag,
     pub name: String,
     pub clan_level: i64,
     pub badge_urls: APIBadge
@@ -121,16 +123,13 @@ pub struct APIPlayerHouseElement {
     pub id: i64,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
-pub enum APIHouseElement {
-    #[serde(rename = "ground")]
-    Ground,
-    #[serde(rename = "walls")]
-    Walls,
-    #[serde(rename = "roof")]
-    Roof,
-    #[serde(rename = "decoration")]
-    Decoration,
+api_enum! {
+    pub enum APIHouseElement {
+        Ground = "ground",
+        Walls = "walls",
+        Roof = "roof",
+        Decoration = "decoration",
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
@@ -146,12 +145,10 @@ pub struct APIPlayerItemLevel {
     pub equipment: Option<Vec<APIPlayerItemLevel>>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
-pub enum APIVillageType {
-    #[serde(rename = "home")]
-    HomeVillage,
-    #[serde(rename = "builderBase")]
-    BuilderBase,
-    #[serde(rename = "clanCapital")]
-    ClanCapital,
+api_enum! {
+    pub enum APIVillageType {
+        HomeVillage = "home",
+        BuilderBase = "builderBase",
+        ClanCapital = "clanCapital",
+    }
 }
@@ -0,0 +1,81 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Request budget of a single API token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    /// Number of requests the bucket refills per second.
+    pub requests_per_second: u32,
+    /// Number of requests that can be sent at once after a quiet period.
+    pub burst: u32,
+}
+
+impl RateLimit {
+    pub fn new(requests_per_second: u32, burst: u32) -> Self {
+        Self {
+            requests_per_second: requests_per_second.max(1),
+            burst: burst.max(1),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Bucket {
+    // may go below zero: every waiting request reserves its token in advance
+    tokens: f64,
+    last_refill: Instant,
+}
+
+/// Token bucket limiter that makes concurrent requests wait for their turn.
+#[derive(Debug)]
+pub struct RateLimiter {
+    limit: RateLimit,
+    bucket: Mutex<Bucket>,
+}
+
+impl RateLimiter {
+    /// Creates a limiter with a full bucket.
+    ///
+    /// Zero rates and bursts are raised to one, like in `RateLimit::new`, since the fields are public.
+    pub fn new(limit: RateLimit) -> Self {
+        let limit = RateLimit::new(limit.requests_per_second, limit.burst);
+        Self {
+            limit,
+            bucket: Mutex::new(Bucket {
+                tokens: limit.burst as f64,
+                last_refill: Instant::now(),
+            }),
+        }
+    }
+
+    /// Returns the limit this limiter enforces.
+    pub fn limit(&self) -> RateLimit {
+        self.limit
+    }
+
+    /// Waits until the request is allowed by the budget.
+    ///
+    /// Requests are served in the order they called `acquire`.
+    pub async fn acquire(&self) {
+        let delay = self.reserve();
+        if !delay.is_zero() {
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    /// Takes a token from the bucket and returns how long the caller has to wait for it.
+    fn reserve(&self) -> Duration {
+        let rate = self.limit.requests_per_second as f64;
+        let mut bucket = self.bucket.lock().expect("Rate limiter lock should not be poisoned");
+        let now = Instant::now();
+        let refilled = now.duration_since(bucket.last_refill).as_secs_f64() * rate;
+        bucket.tokens = (bucket.tokens + refilled).min(self.limit.burst as f64);
+        bucket.last_refill = now;
+        bucket.tokens -= 1.0;
+        if bucket.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-bucket.tokens / rate)
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use crate::api::decode::DecodeDiagnostics;
+use reqwest::header::HeaderMap;
+use serde_json::Value;
+use std::time::{Duration, SystemTime};
+
+/// Response of the API together with everything its model leaves out, see `RestManager::get_raw`.
+#[derive(Debug, Clone)]
+pub struct Response<T> {
+    pub value: T,
+    /// The response as sent by the API, including fields the model does not know.
+    pub json: Value,
+    /// Body of the response; re-serialized from `json` if it was taken from the cache.
+    pub bytes: Vec<u8>,
+    /// None if the response was taken from the cache.
+    pub status: Option<u16>,
+    /// Empty if the response was taken from the cache.
+    pub headers: HeaderMap,
+    /// When the response stops being fresh according to `Cache-Control`; None if it must not be stored.
+    pub expires_at: Option<SystemTime>,
+    /// Differences between the response and the model found while decoding it.
+    pub diagnostics: DecodeDiagnostics,
+}
+
+impl<T> Response<T> {
+    /// Checks whether the response was taken from the cache instead of the API.
+    pub fn is_cached(&self) -> bool {
+        self.status.is_none()
+    }
+
+    /// Returns the value of a response header, e.g. `Date`.
+    pub fn header(&self, name: impl AsRef<str>) -> Option<&str> {
+        self.headers.get(name.as_ref())?.to_str().ok()
+    }
+
+    /// Returns a field of the original response by its JSON pointer, e.g. `/memberList/0/name`.
+    pub fn field(&self, pointer: impl AsRef<str>) -> Option<&Value> {
+        self.json.pointer(pointer.as_ref())
+    }
+
+    /// Returns how long the response stays fresh, zero once it has expired.
+    pub fn max_age(&self) -> Option<Duration> {
+        self.expires_at
+            .map(|expires_at| expires_at.duration_since(SystemTime::now()).unwrap_or_default())
+    }
+
+    /// Returns the typed value, dropping the metadata.
+    pub fn into_value(self) -> T {
+        self.value
+    }
+}
@@ -1,15 +1,37 @@
 use crate::api;
-use crate::errors::{Error, Result};
-use reqwest::header::{HeaderMap, HeaderValue, ACCEPT, AUTHORIZATION};
+use crate::api::cache::{cache_key, Cache, CacheCounters, CacheEntry, CacheMode, CacheStats};
+use crate::api::decode::{decode_tracked, DecodeDiagnostics, DecodeMode};
+use crate::api::instrumentation::{endpoint_template, Call};
+use crate::api::key_pool::{KeyHealth, KeyPool, KeySelection};
+use crate::api::middleware::Middleware;
+use crate::api::rate_limiter::RateLimit;
+use crate::api::response::Response;
+use crate::api::retry::RetryPolicy;
+use crate::api::transport::{HttpRequest, HttpResponse, ReqwestTransport, Transport};
+use crate::errors::{Error, ErrorContext, Result};
+use reqwest::header::{HeaderMap, HeaderValue, ACCEPT, AUTHORIZATION, CACHE_CONTROL, CONTENT_TYPE, RETRY_AFTER, USER_AGENT};
+use reqwest::Method;
 use std::collections::HashMap;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant, SystemTime};
 
 #[derive(Debug, Clone)]
 pub struct RestManagerConfig {
     pub timeout: Duration,
     pub base_url: String,
     pub user_agent: String,
+    pub retry_policy: RetryPolicy,
+    // applied to every token separately, no client-side limit if None
+    pub rate_limit: Option<RateLimit>,
+    pub key_selection: KeySelection,
+    // how long a token is skipped after `accessDenied` or `requestThrottled`
+    pub key_bench_duration: Duration,
+    // responses are not cached if None
+    pub cache: Option<Arc<dyn Cache>>,
+    // `ReqwestTransport` with `timeout` is used if None
+    pub transport: Option<Arc<dyn Transport>>,
+    pub middleware: Vec<Arc<dyn Middleware>>,
+    pub decode_mode: DecodeMode,
 }
 
 impl RestManagerConfig {
@@ -18,6 +40,14 @@ impl RestManagerConfig {
             timeout: Duration::from_secs(timeout),
             base_url: base_url.as_ref().to_string(),
             user_agent: user_agent.as_ref().to_string(),
+            retry_policy: RetryPolicy::default(),
+            rate_limit: None,
+            key_selection: KeySelection::default(),
+            key_bench_duration: Duration::from_secs(60),
+            cache: None,
+            transport: None,
+            middleware: Vec::new(),
+            decode_mode: DecodeMode::default(),
         }
     }
 }
@@ -28,14 +58,40 @@ impl Default for RestManagerConfig {
             timeout: Duration::from_secs(30),
             base_url: "https://api.clashofclans.com/v1".to_string(),
             user_agent: format!("clash-forge/{}", env!("CARGO_PKG_VERSION")),
+            retry_policy: RetryPolicy::default(),
+            rate_limit: None,
+            key_selection: KeySelection::default(),
+            key_bench_duration: Duration::from_secs(60),
+            cache: None,
+            transport: None,
+            middleware: Vec::new(),
+            decode_mode: DecodeMode::default(),
         }
     }
 }
 
 #[derive(Debug, Clone)]
 pub struct RestManager {
-    http_client: reqwest::Client,
+    transport: Arc<dyn Transport>,
+    headers: HeaderMap,
     config: Arc<RestManagerConfig>,
+    // shared between clones, so all of them spend the same budgets
+    keys: Arc<KeyPool>,
+    cache_counters: Arc<CacheCounters>,
+    cache_mode: CacheMode,
+}
+
+/// Successful response of the API.
+#[derive(Debug)]
+struct ApiResponse {
+    value: serde_json::Value,
+    // empty if the response was taken from the cache
+    body: Vec<u8>,
+    // taken from the `Cache-Control` header
+    expires_at: Option<SystemTime>,
+    // None if the response was taken from the cache
+    status: Option<u16>,
+    headers: HeaderMap,
 }
 
 impl RestManager {
@@ -43,52 +99,330 @@ impl RestManager {
         Self::with_config(token, RestManagerConfig::default())
     }
     pub fn with_config(token: impl AsRef<str>, config: RestManagerConfig) -> Result<Self> {
-        let token = token.as_ref();
+        Self::with_tokens([token], config)
+    }
+
+    /// Creates a `RestManager` that distributes requests across several API tokens.
+    ///
+    /// Tokens that respond with `accessDenied` or `requestThrottled` are benched for
+    /// `RestManagerConfig::key_bench_duration` and the request is repeated with another token.
+    pub fn with_tokens<I, S>(tokens: I, config: RestManagerConfig) -> Result<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let keys = KeyPool::new(tokens, config.key_selection, config.key_bench_duration, config.rate_limit)?;
 
         let mut headers = HeaderMap::new();
-        let auth_value = HeaderValue::from_str(&format!("Bearer {token}"))
-            .map_err(|_| Error::InvalidToken)?;
-        headers.insert(AUTHORIZATION, auth_value);
         headers.insert(ACCEPT, HeaderValue::from_static("application/json"));
+        let user_agent = HeaderValue::from_str(&config.user_agent)
+            .map_err(|err| Error::Transport(err.into()))?;
+        headers.insert(USER_AGENT, user_agent);
 
-        let http_client = reqwest::Client::builder()
-            .timeout(config.timeout)
-            .default_headers(headers)
-            .user_agent(&config.user_agent)
-            .build()?;
+        let transport = match &config.transport {
+            Some(transport) => transport.clone(),
+            None => Arc::new(ReqwestTransport::new(config.timeout)?),
+        };
 
         let config = Arc::new(config);
-        Ok(RestManager { http_client, config })
+        Ok(RestManager {
+            transport,
+            headers,
+            config,
+            keys: Arc::new(keys),
+            cache_counters: Arc::default(),
+            cache_mode: CacheMode::default(),
+        })
+    }
+
+    /// Returns a `RestManager` sharing everything with this one, which uses the cache in the given way.
+    ///
+    /// Use it for a single call, e.g. `rest_manager.with_cache_mode(CacheMode::ForceRefresh).player(tag)`.
+    pub fn with_cache_mode(&self, cache_mode: CacheMode) -> Self {
+        Self {
+            cache_mode,
+            ..self.clone()
+        }
+    }
+
+    /// Returns the number of cache hits and misses of this `RestManager` and its clones.
+    pub fn cache_stats(&self) -> CacheStats {
+        self.cache_counters.stats()
+    }
+
+    /// Returns the current state of every API token used by this `RestManager`.
+    pub fn key_health(&self) -> Vec<KeyHealth> {
+        self.keys.health()
     }
     
-    async fn get_data<T>(&self, response: reqwest::Response) -> Result<T>
+    fn get_data(&self, response: &HttpResponse) -> Result<ApiResponse> {
+        if response.status.is_success() {
+            let expires_at = parse_max_age(&response.headers).map(|max_age| SystemTime::now() + max_age);
+            let value = serde_json::from_slice(&response.body)?;
+            Ok(ApiResponse {
+                value,
+                // moved out of the response once it is no longer needed for errors, see `send`
+                body: Vec::new(),
+                expires_at,
+                status: Some(response.status.as_u16()),
+                headers: response.headers.clone(),
+            })
+        } else {
+            Err(Error::Api(api::errors::Error::from_response(response)))
+        }
+    }
+
+    /// Sends the request through the middleware chain and the transport.
+    async fn execute(&self, mut request: HttpRequest) -> Result<HttpResponse> {
+        let started = Instant::now();
+        let mut passed = 0;
+        let mut short_circuit = None;
+        for middleware in &self.config.middleware {
+            passed += 1;
+            short_circuit = middleware.before_request(&mut request);
+            if short_circuit.is_some() {
+                break;
+            }
+        }
+        let mut response = match short_circuit {
+            Some(response) => response,
+            None => self.transport.send(request.clone()).await?,
+        };
+        for middleware in self.config.middleware[..passed].iter().rev() {
+            middleware.after_response(&request, &mut response, started.elapsed());
+        }
+        Ok(response)
+    }
+
+    /// Sends the request, repeating it according to the configured `RetryPolicy`.
+    ///
+    /// Errors carry the context of the request and of the last response.
+    async fn send(&self, request: HttpRequest, path: &str, call: &Call) -> Result<ApiResponse> {
+        let mut last_response = None;
+        match self.send_with_retries(&request, call, &mut last_response).await {
+            Ok(mut response) => {
+                response.body = last_response.map(|response| response.body).unwrap_or_default();
+                Ok(response)
+            }
+            Err(err) => {
+                let context = ErrorContext::new(request.method.as_str(), path, request.query.clone());
+                let context = match last_response {
+                    Some(response) => context.with_response(response.status.as_u16(), response.headers, &response.body),
+                    None => context,
+                };
+                Err(err.with_context(context))
+            }
+        }
+    }
+
+    async fn send_with_retries(&self, request: &HttpRequest, call: &Call, last_response: &mut Option<HttpResponse>) -> Result<ApiResponse> {
+        let policy = &self.config.retry_policy;
+        let mut attempt = 1;
+        loop {
+            let mut retry_after = None;
+            *last_response = None;
+            let key = self.keys.select();
+            key.acquire().await;
+            let mut attempt_request = request.clone();
+            attempt_request.headers.insert(AUTHORIZATION, key.authorization());
+            call.attempt();
+            let result = self.execute(attempt_request).await.and_then(|response| {
+                call.status(response.status.as_u16());
+                retry_after = parse_retry_after(&response.headers);
+                let data = self.get_data(&response);
+                *last_response = Some(response);
+                data
+            });
+            if let Err(Error::Api(err)) = &result
+                && err.reason.is_token_failure()
+            {
+                self.keys.bench(&key);
+                // failover does not count as an attempt, every token is tried at most once
+                if self.keys.has_available() {
+                    continue;
+                }
+            }
+            let err = match result {
+                Err(err) if attempt < policy.max_attempts && policy.is_retryable(&err) => err,
+                result => return result,
+            };
+            match policy.backoff(attempt, retry_after) {
+                Some(delay) => {
+                    call.retry(delay, &err);
+                    tokio::time::sleep(delay).await
+                }
+                None => return Err(err),
+            }
+            attempt += 1;
+        }
+    }
+
+    pub(crate) async fn get<T>(&self, url: &str, parameters: Option<HashMap<String, String>>) -> Result<T>
     where
         T: serde::de::DeserializeOwned,
     {
-        if response.status().is_success() {
-            let data = response.json().await?;
-            Ok(data)
-        } else {
-            Err(Error::Api(api::errors::Error::from_response(response).await))
+        self.get_response(url, parameters).await.map(Response::into_value)
+    }
+
+    /// Retrieves any endpoint along with the original JSON, status, headers and cache expiry of the response.
+    ///
+    /// Use it to read fields the models do not have yet, e.g. with `T` set to `serde_json::Value`.
+    ///
+    /// # Arguments
+    /// * `url` - The path of the endpoint relative to the base URL, e.g. `players/%232PP`.
+    /// * `parameters` - The query parameters of the request.
+    ///
+    /// # Returns
+    /// `Result` containing a `Response` if successful; if an error occurs, it contains an `Error`.
+    pub async fn get_raw<T>(&self, url: impl AsRef<str>, parameters: Option<HashMap<String, String>>) -> Result<Response<T>>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let mut response = self.get_response(url.as_ref(), parameters).await?;
+        if response.is_cached() {
+            response.bytes = response.json.to_string().into_bytes();
         }
+        Ok(response)
     }
 
-    pub(crate) async fn get<T>(&self, url: &str, parameters: Option<HashMap<String, String>>) -> Result<T>
+    /// Retrieves and decodes the response, leaving `Response::bytes` empty for cached responses.
+    async fn get_response<T>(&self, url: &str, parameters: Option<HashMap<String, String>>) -> Result<Response<T>>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let parameters = parameters.unwrap_or_default();
+        let call = Call::start("GET", url);
+        let result = call
+            .instrument(self.get_value(url, parameters.clone(), &call))
+            .await
+            .and_then(|response| self.decode(response, ErrorContext::new("GET", url, parameters)));
+        call.finish(&result);
+        result
+    }
+
+    /// Returns the response from the cache or the API.
+    async fn get_value(&self, url: &str, parameters: HashMap<String, String>, call: &Call) -> Result<ApiResponse> {
+        let cache = self.config.cache.as_ref().filter(|_| self.cache_mode != CacheMode::Bypass);
+        let key = cache_key(url, &parameters);
+        if let Some(cache) = cache.filter(|_| self.cache_mode == CacheMode::Default) {
+            match cache.get(&key).await {
+                Some(entry) if !entry.is_expired() => {
+                    self.cache_counters.hit();
+                    call.cache_hit(true);
+                    return Ok(ApiResponse {
+                        value: entry.value,
+                        body: Vec::new(),
+                        expires_at: Some(entry.expires_at),
+                        status: None,
+                        headers: HeaderMap::new(),
+                    });
+                }
+                _ => {
+                    self.cache_counters.miss();
+                    call.cache_hit(false);
+                }
+            }
+        }
+
+        let request = HttpRequest {
+            method: Method::GET,
+            url: format!("{}/{}", self.config.base_url, url),
+            query: parameters,
+            headers: self.headers.clone(),
+            body: None,
+        };
+        let response = self.send(request, url, call).await?;
+        if let Some((cache, expires_at)) = cache.zip(response.expires_at) {
+            let entry = CacheEntry {
+                value: response.value.clone(),
+                expires_at,
+            };
+            cache.insert(key, entry).await;
+        }
+        Ok(response)
+    }
+
+    /// Deserializes the response and checks it against the model according to the `DecodeMode`.
+    ///
+    /// Failures carry the context and the response JSON.
+    fn decode<T>(&self, response: ApiResponse, context: ErrorContext) -> Result<Response<T>>
     where
         T: serde::de::DeserializeOwned,
     {
-        let url = format!("{}/{}", self.config.base_url, url);
-        let response = self.http_client.get(url).query(&parameters.unwrap_or_default()).send().await?;
-        self.get_data(response).await
+        let mut diagnostics = DecodeDiagnostics::new(endpoint_template(&context.endpoint));
+        // only the other modes pay for tracking the fields
+        let result = match self.config.decode_mode {
+            DecodeMode::Standard => T::deserialize(&response.value),
+            DecodeMode::Lenient | DecodeMode::Strict => decode_tracked(&response.value, &mut diagnostics),
+        };
+        let result = match result.map_err(Error::from) {
+            Ok(_) if self.config.decode_mode == DecodeMode::Strict && !diagnostics.is_empty() => Err(Error::Decode(Box::new(diagnostics.clone()))),
+            result => result,
+        };
+        match result {
+            Ok(value) => Ok(Response {
+                value,
+                json: response.value,
+                bytes: response.body,
+                status: response.status,
+                headers: response.headers,
+                expires_at: response.expires_at,
+                diagnostics,
+            }),
+            Err(err) => {
+                let context = match response.status {
+                    Some(status) => context.with_response(status, response.headers, &response.body),
+                    None => ErrorContext { body: Some(response.value.to_string()), ..context },
+                };
+                Err(err.with_context(context))
+            }
+        }
     }
 
+    // only players have POST endpoints
+    #[cfg_attr(not(feature = "players"), allow(dead_code))]
     pub(crate) async fn post<T, K>(&self, url: &str, json: &T) -> Result<K>
     where
         T: serde::Serialize,
         K: serde::de::DeserializeOwned,
     {
-        let url = format!("{}/{}", self.config.base_url, url);
-        let response = self.http_client.post(url).json(json).send().await?;
-        self.get_data(response).await
+        let mut headers = self.headers.clone();
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        let request = HttpRequest {
+            method: Method::POST,
+            url: format!("{}/{}", self.config.base_url, url),
+            query: HashMap::new(),
+            headers,
+            body: Some(serde_json::to_vec(json)?),
+        };
+        let call = Call::start("POST", url);
+        let result = call
+            .instrument(self.send(request, url, &call))
+            .await
+            .and_then(|response| self.decode(response, ErrorContext::new("POST", url, HashMap::new())));
+        call.finish(&result);
+        result.map(Response::into_value)
+    }
+}
+
+/// Reads `max-age` from the `Cache-Control` header; responses that must not be stored have none.
+fn parse_max_age(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(CACHE_CONTROL)?.to_str().ok()?;
+    // the API separates directives with spaces instead of commas
+    let directives: Vec<_> = value.split([',', ' ']).filter(|directive| !directive.is_empty()).collect();
+    if directives.iter().any(|directive| directive.eq_ignore_ascii_case("no-store") || directive.eq_ignore_ascii_case("no-cache")) {
+        return None;
     }
+    directives
+        .iter()
+        .find_map(|directive| directive.strip_prefix("max-age="))
+        .and_then(|seconds| seconds.parse().ok())
+        .filter(|seconds| *seconds > 0)
+        .map(Duration::from_secs)
+}
+
+/// Reads the `Retry-After` header, which the API sends as a number of seconds.
+fn parse_retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?;
+    value.trim().parse().ok().map(Duration::from_secs)
 }
@@ -0,0 +1,162 @@
+use crate::api::errors::ApiErrorReason;
+use crate::errors::Error;
+use std::time::Duration;
+
+/// Policy that decides whether and when a failed request is sent again.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first one. `1` disables retrying.
+    pub max_attempts: u32,
+    /// Delay before the first retry.
+    pub initial_backoff: Duration,
+    /// Upper bound for a single delay between attempts.
+    pub max_backoff: Duration,
+    /// Factor the delay is multiplied by after every attempt.
+    pub multiplier: u32,
+    /// Randomizes every delay between half and the full computed value.
+    pub jitter: bool,
+    /// API error reasons that are worth retrying.
+    pub retryable_reasons: Vec<ApiErrorReason>,
+    /// Retries timeouts, connection failures and errors of custom transports.
+    pub retry_on_http_errors: bool,
+    /// Waits for the duration from the `Retry-After` header when the API provides it.
+    pub respect_retry_after: bool,
+}
+
+impl RetryPolicy {
+    /// Creates a `RetryPolicyBuilder` to configure `RetryPolicy`.
+    ///
+    /// This is the same as `RetryPolicyBuilder::new()`.
+    pub fn builder() -> RetryPolicyBuilder {
+        RetryPolicyBuilder::new()
+    }
+
+    /// Returns a policy that never retries.
+    pub fn disabled() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    /// Checks whether the error is worth another attempt according to this policy.
+    pub fn is_retryable(&self, error: &Error) -> bool {
+        match error.inner() {
+            Error::Api(err) => self.retryable_reasons.contains(&err.reason),
+            Error::Http(_) | Error::Transport(_) => self.retry_on_http_errors && error.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// Computes the delay before the next attempt.
+    ///
+    /// # Arguments
+    /// * `attempt` - The number of the attempt that just failed, starting from `1`.
+    /// * `retry_after` - The value of the `Retry-After` header of the failed response, if any.
+    ///
+    /// # Returns
+    /// `None` if the server asked to wait longer than `max_backoff`, otherwise the delay to wait.
+    pub fn backoff(&self, attempt: u32, retry_after: Option<Duration>) -> Option<Duration> {
+        if let Some(retry_after) = retry_after.filter(|_| self.respect_retry_after) {
+            return (retry_after <= self.max_backoff).then_some(retry_after);
+        }
+        let factor = self.multiplier.saturating_pow(attempt.saturating_sub(1));
+        let delay = self.initial_backoff.saturating_mul(factor).min(self.max_backoff);
+        if self.jitter && !delay.is_zero() {
+            let half = delay / 2;
+            let spread = rand::random_range(0..=half.as_millis() as u64);
+            Some(half + Duration::from_millis(spread))
+        } else {
+            Some(delay)
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            multiplier: 2,
+            jitter: true,
+            retryable_reasons: vec![
+                ApiErrorReason::RequestThrottled,
+                ApiErrorReason::InMaintenance,
+                ApiErrorReason::RequestAborted,
+            ],
+            retry_on_http_errors: true,
+            respect_retry_after: true,
+        }
+    }
+}
+
+/// Builder for `RetryPolicy`.
+///
+/// Starts from `RetryPolicy::default()`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct RetryPolicyBuilder {
+    policy: RetryPolicy,
+}
+
+impl RetryPolicyBuilder {
+    /// Constructs a new `RetryPolicyBuilder`.
+    ///
+    /// This is the same as `RetryPolicy::builder()`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the total number of attempts, including the first one.
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.policy.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Sets the delay before the first retry.
+    pub fn initial_backoff(mut self, initial_backoff: Duration) -> Self {
+        self.policy.initial_backoff = initial_backoff;
+        self
+    }
+
+    /// Sets the upper bound for a single delay between attempts.
+    pub fn max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.policy.max_backoff = max_backoff;
+        self
+    }
+
+    /// Sets the factor the delay is multiplied by after every attempt.
+    pub fn multiplier(mut self, multiplier: u32) -> Self {
+        self.policy.multiplier = multiplier;
+        self
+    }
+
+    /// Enables or disables randomization of delays.
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.policy.jitter = jitter;
+        self
+    }
+
+    /// Sets the API error reasons that are worth retrying.
+    pub fn retryable_reasons(mut self, reasons: Vec<ApiErrorReason>) -> Self {
+        self.policy.retryable_reasons = reasons;
+        self
+    }
+
+    /// Enables or disables retrying of transport errors.
+    pub fn retry_on_http_errors(mut self, retry_on_http_errors: bool) -> Self {
+        self.policy.retry_on_http_errors = retry_on_http_errors;
+        self
+    }
+
+    /// Enables or disables honoring the `Retry-After` header.
+    pub fn respect_retry_after(mut self, respect_retry_after: bool) -> Self {
+        self.policy.respect_retry_after = respect_retry_after;
+        self
+    }
+
+    /// Returns the `RetryPolicy` that uses `RetryPolicyBuilder` configuration.
+    pub fn build(self) -> RetryPolicy {
+        self.policy
+    }
+}
@@ -0,0 +1,166 @@
+use crate::errors::Result;
+use async_trait::async_trait;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION};
+use reqwest::{Method, StatusCode};
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Request prepared by `RestManager`, including authorization and other headers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpRequest {
+    pub method: Method,
+    /// Absolute URL without the query string.
+    pub url: String,
+    pub query: HashMap<String, String>,
+    pub headers: HeaderMap,
+    pub body: Option<Vec<u8>>,
+}
+
+impl HttpRequest {
+    /// Returns the headers with the API token hidden, suitable for logging.
+    pub fn redacted_headers(&self) -> HeaderMap {
+        let mut headers = self.headers.clone();
+        if headers.contains_key(AUTHORIZATION) {
+            headers.insert(AUTHORIZATION, HeaderValue::from_static("Bearer <redacted>"));
+        }
+        headers
+    }
+}
+
+/// Response returned by a `Transport`, whatever its status.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: Vec<u8>,
+}
+
+impl HttpResponse {
+    /// Creates a response without headers.
+    pub fn new(status: u16, body: impl Into<Vec<u8>>) -> Self {
+        Self {
+            status: StatusCode::from_u16(status).expect("Status code should be valid"),
+            headers: HeaderMap::new(),
+            body: body.into(),
+        }
+    }
+
+    /// Adds a header to the response.
+    pub fn with_header(mut self, name: &'static str, value: impl AsRef<str>) -> Self {
+        let value = HeaderValue::from_str(value.as_ref()).expect("Header value should be valid");
+        self.headers.append(HeaderName::from_static(name), value);
+        self
+    }
+
+    pub(crate) async fn from_reqwest(response: reqwest::Response) -> Result<Self> {
+        let status = response.status();
+        let headers = response.headers().clone();
+        let body = response.bytes().await?.to_vec();
+        Ok(Self { status, headers, body })
+    }
+}
+
+/// Sends requests of `RestManager` over the network or anywhere else.
+///
+/// Transport errors are reported as `Error::Http` or `Error::Transport`;
+/// responses with error statuses are returned as `Ok`.
+#[async_trait]
+pub trait Transport: fmt::Debug + Send + Sync {
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse>;
+}
+
+/// `Transport` backed by a `reqwest::Client`, used by default.
+#[derive(Debug, Clone)]
+pub struct ReqwestTransport {
+    http_client: reqwest::Client,
+}
+
+impl ReqwestTransport {
+    /// Creates a transport with a new client.
+    pub fn new(timeout: Duration) -> Result<Self> {
+        let http_client = reqwest::Client::builder().timeout(timeout).build()?;
+        Ok(Self::from_client(http_client))
+    }
+
+    /// Creates a transport with a pre-configured client, e.g. with proxies or custom TLS roots.
+    pub fn from_client(http_client: reqwest::Client) -> Self {
+        Self { http_client }
+    }
+}
+
+#[async_trait]
+impl Transport for ReqwestTransport {
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse> {
+        let mut builder = self
+            .http_client
+            .request(request.method, request.url)
+            .query(&request.query)
+            .headers(request.headers);
+        if let Some(body) = request.body {
+            builder = builder.body(body);
+        }
+        HttpResponse::from_reqwest(builder.send().await?).await
+    }
+}
+
+/// `Transport` that answers with registered responses and records every request.
+///
+/// Responses registered for the same route are returned in order, the last one is repeated.
+/// Requests without a registered response get `404 notFound`.
+#[derive(Debug, Default)]
+pub struct InMemoryTransport {
+    routes: Mutex<Vec<(Method, String, VecDeque<HttpResponse>)>>,
+    requests: Mutex<Vec<HttpRequest>>,
+}
+
+impl InMemoryTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a response for requests whose URL ends with the path.
+    ///
+    /// # Arguments
+    /// * `method` - The method of the request.
+    /// * `path` - The path of the endpoint, e.g. `goldpass/seasons/current`.
+    /// * `response` - The response to return.
+    pub fn push_response(&self, method: Method, path: impl AsRef<str>, response: HttpResponse) {
+        let path = format!("/{}", path.as_ref().trim_start_matches('/'));
+        let mut routes = self.routes.lock().expect("Routes lock should not be poisoned");
+        match routes.iter_mut().find(|(route_method, route_path, _)| *route_method == method && *route_path == path) {
+            Some((_, _, responses)) => responses.push_back(response),
+            None => routes.push((method, path, VecDeque::from([response]))),
+        }
+    }
+
+    /// Returns all requests sent so far.
+    pub fn requests(&self) -> Vec<HttpRequest> {
+        self.requests.lock().expect("Requests lock should not be poisoned").clone()
+    }
+}
+
+#[async_trait]
+impl Transport for InMemoryTransport {
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse> {
+        let mut routes = self.routes.lock().expect("Routes lock should not be poisoned");
+        let responses = routes
+            .iter_mut()
+            .find(|(method, path, _)| *method == request.method && request.url.ends_with(path.as_str()))
+            .map(|(_, _, responses)| responses);
+        let response = match responses {
+            Some(responses) if responses.len() > 1 => responses.pop_front(),
+            Some(responses) => responses.front().cloned(),
+            None => None,
+        }
+        .unwrap_or_else(|| {
+            let body = format!(r#"{{"reason":"notFound","message":"No response registered for {}"}}"#, request.url);
+            HttpResponse::new(404, body)
+        });
+        drop(routes);
+
+        self.requests.lock().expect("Requests lock should not be poisoned").push(request);
+        Ok(response)
+    }
+}
@@ -1,40 +1,261 @@
+#[cfg(feature = "http")]
+use reqwest::header::HeaderMap;
+#[cfg(feature = "http")]
+use std::collections::HashMap;
 use std::fmt;
 
+/// Response bodies longer than this are truncated in `ErrorContext`.
+#[cfg(feature = "http")]
+const MAX_CONTEXT_BODY_LEN: usize = 2048;
+
+/// Details of the request that failed, enough to diagnose the error from logs.
+#[cfg(feature = "http")]
+#[derive(Debug, Clone, Default)]
+pub struct ErrorContext {
+    pub method: String,
+    /// Path relative to the base URL, e.g. `players/%23ABC`.
+    pub endpoint: String,
+    /// Tag found in the path, e.g. `#ABC`.
+    pub tag: Option<String>,
+    pub query: HashMap<String, String>,
+    // None if no response was received
+    pub status: Option<u16>,
+    /// Response body, truncated to a few kilobytes.
+    pub body: Option<String>,
+    /// Response headers, e.g. `Retry-After`.
+    pub headers: HeaderMap,
+}
+
+#[cfg(feature = "http")]
+impl ErrorContext {
+    /// Creates the context of a request to the endpoint.
+    ///
+    /// # Arguments
+    /// * `method` - The method of the request.
+    /// * `endpoint` - The path of the endpoint relative to the base URL.
+    /// * `query` - The query parameters of the request.
+    pub fn new(method: impl AsRef<str>, endpoint: impl AsRef<str>, query: HashMap<String, String>) -> Self {
+        let endpoint = endpoint.as_ref().to_string();
+        let tag = endpoint
+            .split('/')
+            .find(|segment| segment.starts_with("%23") || segment.starts_with('#'))
+            .map(|segment| urlencoding::decode(segment).map_or_else(|_| segment.to_string(), |tag| tag.into_owned()));
+        Self {
+            method: method.as_ref().to_string(),
+            endpoint,
+            tag,
+            query,
+            ..Self::default()
+        }
+    }
+
+    /// Adds the status, headers and body of the response.
+    pub fn with_response(mut self, status: u16, headers: HeaderMap, body: &[u8]) -> Self {
+        self.status = Some(status);
+        self.headers = headers;
+        self.body = Some(truncate_body(&String::from_utf8_lossy(body)));
+        self
+    }
+
+    /// Returns the value of a response header, e.g. `Retry-After`.
+    pub fn header(&self, name: impl AsRef<str>) -> Option<&str> {
+        self.headers.get(name.as_ref())?.to_str().ok()
+    }
+}
+
+#[cfg(feature = "http")]
+impl fmt::Display for ErrorContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.method, self.endpoint)?;
+        if !self.query.is_empty() {
+            let mut query: Vec<_> = self.query.iter().map(|(name, value)| format!("{name}={value}")).collect();
+            query.sort();
+            write!(f, "?{}", query.join("&"))?;
+        }
+        if let Some(status) = self.status {
+            write!(f, ", status {status}")?;
+        }
+        if let Some(retry_after) = self.header("retry-after") {
+            write!(f, ", retry after {retry_after}s")?;
+        }
+        if let Some(body) = &self.body {
+            write!(f, ", body: {body}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "http")]
+fn truncate_body(body: &str) -> String {
+    match body.char_indices().nth(MAX_CONTEXT_BODY_LEN) {
+        Some((end, _)) => format!("{}... ({} bytes total)", &body[..end], body.len()),
+        None => body.to_string(),
+    }
+}
+
+/// Errors of the crate.
+///
+/// Errors returned by `RestManager` calls are wrapped in `Error::Request` with the request they happened in,
+/// so match on `Error::inner()` instead of the error itself, e.g. `matches!(err.inner(), Error::Api(_))`.
+/// New variants may be added in minor releases, and `Http`, `Transport`, `Decode` and `Request` only exist
+/// with the `http` feature and `InvalidInterval` with the `events` feature, so matches need a wildcard arm.
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum Error {
+    #[cfg(feature = "http")]
     Http(reqwest::Error),
+    // errors of custom transports
+    #[cfg(feature = "http")]
+    Transport(Box<dyn std::error::Error + Send + Sync>),
     Json(serde_json::Error),
+    // response that does not match its model, see `DecodeMode`
+    #[cfg(feature = "http")]
+    Decode(Box<crate::api::decode::DecodeDiagnostics>),
     Api(crate::api::errors::Error),
     InvalidToken,
+    // tag containing characters outside of `TAG_ALPHABET`
+    InvalidTag(String),
+    // time in another format than `20250101T080000.000Z`
+    InvalidTimestamp(String),
+    KeyManager(String),
+    InvalidCheckpoint(String),
+    // zero time between the polls of an event poller
+    #[cfg(feature = "events")]
+    InvalidInterval,
+    // error of a `RestManager` call, with the request it happened in
+    #[cfg(feature = "http")]
+    Request {
+        context: Box<ErrorContext>,
+        source: Box<Error>,
+    },
 }
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
+            #[cfg(feature = "http")]
             Error::Http(err) => write!(f, "HTTP error: {err}"),
+            #[cfg(feature = "http")]
+            Error::Transport(err) => write!(f, "Transport error: {err}"),
             Error::Json(err) => write!(f, "JSON error: {err}"),
+            #[cfg(feature = "http")]
+            Error::Decode(diagnostics) => write!(f, "Response does not match the model: {diagnostics}"),
             Error::Api(err) => write!(f, "API error: {err}"),
             Error::InvalidToken => write!(f, "Invalid API token"),
+            Error::InvalidTag(tag) => write!(f, "Invalid tag: {tag}"),
+            Error::InvalidTimestamp(time) => write!(f, "Invalid timestamp: {time}"),
+            Error::KeyManager(message) => write!(f, "Key manager error: {message}"),
+            Error::InvalidCheckpoint(message) => write!(f, "Invalid pagination checkpoint: {message}"),
+            #[cfg(feature = "events")]
+            Error::InvalidInterval => write!(f, "Invalid poll interval: it must be greater than zero"),
+            #[cfg(feature = "http")]
+            Error::Request { context, source } => write!(f, "{source} ({context})"),
+        }
+    }
+}
+
+impl Error {
+    /// Attaches the context of the request to the error, unless it already has one.
+    #[cfg(feature = "http")]
+    pub fn with_context(self, context: ErrorContext) -> Self {
+        match self {
+            Error::Request { .. } => self,
+            source => Error::Request {
+                context: Box::new(context),
+                source: Box::new(source),
+            },
+        }
+    }
+
+    /// Returns the context of the request the error happened in, if known.
+    #[cfg(feature = "http")]
+    pub fn context(&self) -> Option<&ErrorContext> {
+        match self {
+            Error::Request { context, .. } => Some(context),
+            _ => None,
+        }
+    }
+
+    /// Returns the error without its request context.
+    pub fn inner(&self) -> &Error {
+        match self {
+            #[cfg(feature = "http")]
+            Error::Request { source, .. } => source.inner(),
+            _ => self,
+        }
+    }
+
+    /// Returns the error reported by the API, if any.
+    pub fn api_error(&self) -> Option<&crate::api::errors::Error> {
+        match self.inner() {
+            Error::Api(err) => Some(err),
+            _ => None,
+        }
+    }
+
+    /// Returns the reason of the error if it was reported by the API.
+    pub fn api_reason(&self) -> Option<&crate::api::errors::ApiErrorReason> {
+        self.api_error().map(|err| &err.reason)
+    }
+
+    /// Checks whether the same request may succeed later, e.g. after throttling, maintenance or a timeout.
+    pub fn is_retryable(&self) -> bool {
+        match self.inner() {
+            Error::Api(err) => err.is_retryable(),
+            #[cfg(feature = "http")]
+            Error::Http(err) => err.is_timeout() || err.is_connect() || err.is_request(),
+            #[cfg(feature = "http")]
+            Error::Transport(_) => true,
+            _ => false,
         }
     }
+
+    pub fn is_not_found(&self) -> bool {
+        self.api_error().is_some_and(|err| err.is_not_found())
+    }
+
+    pub fn is_maintenance(&self) -> bool {
+        self.api_error().is_some_and(|err| err.is_maintenance())
+    }
+
+    pub fn is_access_denied(&self) -> bool {
+        self.api_error().is_some_and(|err| err.is_access_denied())
+    }
+
+    pub fn is_throttled(&self) -> bool {
+        self.api_error().is_some_and(|err| err.is_throttled())
+    }
 }
 
 impl std::error::Error for Error {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
+            #[cfg(feature = "http")]
             Error::Http(err) => Some(err),
+            #[cfg(feature = "http")]
+            Error::Transport(err) => Some(err.as_ref()),
             Error::Json(err) => Some(err),
+            #[cfg(feature = "http")]
+            Error::Request { source, .. } => Some(source.as_ref()),
             _ => None,
         }
     }
 }
 
+#[cfg(feature = "http")]
 impl From<reqwest::Error> for Error {
     fn from(err: reqwest::Error) -> Self {
         Error::Http(err)
     }
 }
 
+#[cfg(feature = "http")]
+impl From<Box<dyn std::error::Error + Send + Sync>> for Error {
+    fn from(err: Box<dyn std::error::Error + Send + Sync>) -> Self {
+        Error::Transport(err)
+    }
+}
+
 impl From<serde_json::Error> for Error {
     fn from(err: serde_json::Error) -> Self {
         Error::Json(err)
@@ -0,0 +1,258 @@
+use crate::api::clans::models::{APIClan, APIClanDistrictData, APILanguage, APIWarFrequency};
+use crate::api::common::tags::ClanTag;
+use crate::api::labels::models::APILabel;
+use crate::api::leagues::models::{APICapitalLeague, APIWarLeague};
+use crate::api::locations::models::APILocation;
+use crate::api::rest_manager::RestManager;
+use crate::errors::Result;
+use crate::events::poller::{impl_poller, poll_each, PollTargets};
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+/// Change of the settings or progress of a clan between two polls.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClanEvent {
+    DescriptionChanged {
+        clan: ClanTag,
+        from: Option<String>,
+        to: Option<String>,
+    },
+    LabelsChanged {
+        clan: ClanTag,
+        added: Vec<APILabel>,
+        removed: Vec<APILabel>,
+    },
+    RequiredTrophiesChanged {
+        clan: ClanTag,
+        from: i64,
+        to: i64,
+    },
+    RequiredTownHallLevelChanged {
+        clan: ClanTag,
+        from: i64,
+        to: i64,
+    },
+    WarFrequencyChanged {
+        clan: ClanTag,
+        from: APIWarFrequency,
+        to: APIWarFrequency,
+    },
+    WarLogVisibilityChanged {
+        clan: ClanTag,
+        is_war_log_public: bool,
+    },
+    WarLeagueChanged {
+        clan: ClanTag,
+        from: APIWarLeague,
+        to: APIWarLeague,
+    },
+    CapitalLeagueChanged {
+        clan: ClanTag,
+        from: Option<APICapitalLeague>,
+        to: Option<APICapitalLeague>,
+    },
+    ClanLevelChanged {
+        clan: ClanTag,
+        from: i64,
+        to: i64,
+    },
+    LocationChanged {
+        clan: ClanTag,
+        from: Option<APILocation>,
+        to: Option<APILocation>,
+    },
+    ChatLanguageChanged {
+        clan: ClanTag,
+        from: Option<APILanguage>,
+        to: Option<APILanguage>,
+    },
+    /// `from` is 0 for a district that was unlocked.
+    DistrictHallUpgraded {
+        clan: ClanTag,
+        district: APIClanDistrictData,
+        from: i64,
+        to: i64,
+    },
+}
+
+impl ClanEvent {
+    /// Returns the tag of the clan the event happened in.
+    pub fn clan(&self) -> &ClanTag {
+        match self {
+            ClanEvent::DescriptionChanged { clan, .. }
+            | ClanEvent::LabelsChanged { clan, .. }
+            | ClanEvent::RequiredTrophiesChanged { clan, .. }
+            | ClanEvent::RequiredTownHallLevelChanged { clan, .. }
+            | ClanEvent::WarFrequencyChanged { clan, .. }
+            | ClanEvent::WarLogVisibilityChanged { clan, .. }
+            | ClanEvent::WarLeagueChanged { clan, .. }
+            | ClanEvent::CapitalLeagueChanged { clan, .. }
+            | ClanEvent::ClanLevelChanged { clan, .. }
+            | ClanEvent::LocationChanged { clan, .. }
+            | ClanEvent::ChatLanguageChanged { clan, .. }
+            | ClanEvent::DistrictHallUpgraded { clan, .. } => clan,
+        }
+    }
+}
+
+/// Compares two snapshots of a clan.
+///
+/// Leagues, the location and the chat language are compared by their IDs, labels by the set of IDs.
+///
+/// # Arguments
+/// * `previous` - The clan at the previous poll.
+/// * `current` - The clan at the latest poll.
+pub fn diff_clan(previous: &APIClan, current: &APIClan) -> Vec<ClanEvent> {
+    let clan = || current.tag.clone();
+    let mut events = Vec::new();
+    if previous.description != current.description {
+        events.push(ClanEvent::DescriptionChanged {
+            clan: clan(),
+            from: previous.description.clone(),
+            to: current.description.clone(),
+        });
+    }
+
+    let label_ids = |labels: &[APILabel]| labels.iter().map(|label| label.id).collect::<HashSet<_>>();
+    let (previous_labels, current_labels) = (label_ids(&previous.labels), label_ids(&current.labels));
+    let added: Vec<_> = current.labels.iter().filter(|label| !previous_labels.contains(&label.id)).cloned().collect();
+    let removed: Vec<_> = previous.labels.iter().filter(|label| !current_labels.contains(&label.id)).cloned().collect();
+    if !added.is_empty() || !removed.is_empty() {
+        events.push(ClanEvent::LabelsChanged { clan: clan(), added, removed });
+    }
+
+    if previous.required_trophies != current.required_trophies {
+        events.push(ClanEvent::RequiredTrophiesChanged {
+            clan: clan(),
+            from: previous.required_trophies,
+            to: current.required_trophies,
+        });
+    }
+    if previous.required_townhall_level != current.required_townhall_level {
+        events.push(ClanEvent::RequiredTownHallLevelChanged {
+            clan: clan(),
+            from: previous.required_townhall_level,
+            to: current.required_townhall_level,
+        });
+    }
+    if previous.war_frequency != current.war_frequency {
+        events.push(ClanEvent::WarFrequencyChanged {
+            clan: clan(),
+            from: previous.war_frequency.clone(),
+            to: current.war_frequency.clone(),
+        });
+    }
+    if previous.is_war_log_public != current.is_war_log_public {
+        events.push(ClanEvent::WarLogVisibilityChanged {
+            clan: clan(),
+            is_war_log_public: current.is_war_log_public,
+        });
+    }
+    if previous.war_league.id != current.war_league.id {
+        events.push(ClanEvent::WarLeagueChanged {
+            clan: clan(),
+            from: previous.war_league.clone(),
+            to: current.war_league.clone(),
+        });
+    }
+    let capital_league_id = |clan: &APIClan| clan.capital_league.as_ref().map(|league| league.id);
+    if capital_league_id(previous) != capital_league_id(current) {
+        events.push(ClanEvent::CapitalLeagueChanged {
+            clan: clan(),
+            from: previous.capital_league.clone(),
+            to: current.capital_league.clone(),
+        });
+    }
+    if previous.clan_level != current.clan_level {
+        events.push(ClanEvent::ClanLevelChanged {
+            clan: clan(),
+            from: previous.clan_level,
+            to: current.clan_level,
+        });
+    }
+    let location_id = |clan: &APIClan| clan.location.as_ref().map(|location| location.id);
+    if location_id(previous) != location_id(current) {
+        events.push(ClanEvent::LocationChanged {
+            clan: clan(),
+            from: previous.location.clone(),
+            to: current.location.clone(),
+        });
+    }
+    let language_id = |clan: &APIClan| clan.chat_language.as_ref().map(|language| language.id);
+    if language_id(previous) != language_id(current) {
+        events.push(ClanEvent::ChatLanguageChanged {
+            clan: clan(),
+            from: previous.chat_language.clone(),
+            to: current.chat_language.clone(),
+        });
+    }
+
+    let districts = |clan: &APIClan| clan.clan_capital.as_ref().map(|capital| capital.districts.clone()).unwrap_or_default();
+    let previous_districts = districts(previous);
+    for district in districts(current) {
+        let from = previous_districts
+            .iter()
+            .find(|previous| previous.id == district.id)
+            .map_or(0, |previous| previous.district_hall_level);
+        if district.district_hall_level > from {
+            events.push(ClanEvent::DistrictHallUpgraded {
+                clan: clan(),
+                from,
+                to: district.district_hall_level,
+                district,
+            });
+        }
+    }
+    events
+}
+
+/// Polls the information of clans and emits `ClanEvent`s.
+///
+/// The first poll of a clan only records it.
+#[derive(Debug)]
+pub struct ClanPoller {
+    clans: Vec<ClanTag>,
+    interval: Duration,
+    // every clan at the previous poll
+    snapshots: HashMap<ClanTag, APIClan>,
+}
+
+impl ClanPoller {
+    /// Retrieves every clan once and returns the changes since the previous poll.
+    ///
+    /// # Arguments
+    /// * `rest_manager` - The `RestManager` used for the requests.
+    ///
+    /// # Returns
+    /// Events of all clans, followed by the errors of the clans that could not be retrieved.
+    pub async fn poll(&mut self, rest_manager: &RestManager) -> Vec<Result<ClanEvent>> {
+        poll_each(&self.clans, |tag| rest_manager.clan_info(tag), |tag, clan| {
+            let events = self.snapshots.get(tag).map(|previous| diff_clan(previous, &clan)).unwrap_or_default();
+            self.snapshots.insert(tag.clone(), clan);
+            events
+        })
+        .await
+    }
+}
+
+/// Builder for `ClanPoller`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ClanPollerBuilder {
+    targets: PollTargets,
+}
+
+impl ClanPollerBuilder {
+    /// Returns the `ClanPoller` that uses `ClanPollerBuilder` configuration.
+    ///
+    /// # Returns
+    /// `Result` containing a `ClanPoller` if successful; if a tag is invalid or the interval is zero, it contains an `Error`.
+    pub fn build(self) -> Result<ClanPoller> {
+        Ok(ClanPoller {
+            clans: self.targets.tags()?,
+            interval: self.targets.interval()?,
+            snapshots: HashMap::new(),
+        })
+    }
+}
+
+impl_poller!(ClanPoller, ClanPollerBuilder, ClanEvent, clan, clans);
@@ -0,0 +1,295 @@
+use crate::api::clans::models::APIClanMember;
+use crate::api::common::tags::{ClanTag, PlayerTag};
+use crate::api::rest_manager::RestManager;
+use crate::errors::Result;
+use crate::events::poller::{impl_poller, poll_each, PollTargets};
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+// seasons end on the last Monday of the month at this hour (UTC)
+const RESET_HOUR: u64 = 5;
+// the API may reset the counters late, after this the reset is assumed without a drop
+const RESET_GRACE: Duration = Duration::from_secs(SECONDS_PER_DAY);
+
+/// Donations of a member in the current season.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct DonationTotals {
+    pub donated: i64,
+    pub received: i64,
+}
+
+impl DonationTotals {
+    /// Returns the ratio of donated to received troops, or `None` if nothing was received.
+    pub fn ratio(&self) -> Option<f64> {
+        (self.received > 0).then(|| self.donated as f64 / self.received as f64)
+    }
+}
+
+/// Donations in a clan between two polls.
+///
+/// `totals` are the totals of the member in the current season, including `amount`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DonationEvent {
+    Donated {
+        clan: ClanTag,
+        member: APIClanMember,
+        amount: i64,
+        totals: DonationTotals,
+    },
+    Received {
+        clan: ClanTag,
+        member: APIClanMember,
+        amount: i64,
+        totals: DonationTotals,
+    },
+    /// Emitted before the donations of the new season, with the final totals of every member of the ended season.
+    SeasonEnded {
+        clan: ClanTag,
+        totals: HashMap<PlayerTag, DonationTotals>,
+    },
+}
+
+impl DonationEvent {
+    /// Returns the tag of the clan the event happened in.
+    pub fn clan(&self) -> &ClanTag {
+        match self {
+            DonationEvent::Donated { clan, .. } | DonationEvent::Received { clan, .. } | DonationEvent::SeasonEnded { clan, .. } => clan,
+        }
+    }
+}
+
+/// Returns the end of the season that `time` belongs to.
+///
+/// Seasons end on the last Monday of the month at 05:00 UTC.
+pub fn season_end(time: SystemTime) -> SystemTime {
+    let seconds = time.duration_since(UNIX_EPOCH).map_or(0, |duration| duration.as_secs());
+    let (mut year, mut month) = civil_from_days((seconds / SECONDS_PER_DAY) as i64);
+    loop {
+        let end = UNIX_EPOCH + Duration::from_secs(last_monday(year, month) as u64 * SECONDS_PER_DAY + RESET_HOUR * 60 * 60);
+        if end > time {
+            return end;
+        }
+        (year, month) = if month == 12 { (year + 1, 1) } else { (year, month + 1) };
+    }
+}
+
+// days since the epoch of the last Monday of the month
+fn last_monday(year: i64, month: u32) -> i64 {
+    let next_month = if month == 12 { days_from_civil(year + 1, 1, 1) } else { days_from_civil(year, month + 1, 1) };
+    let last_day = next_month - 1;
+    // the epoch was a Thursday
+    last_day - (last_day + 3).rem_euclid(7)
+}
+
+// http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let month = i64::from(month);
+    let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+// http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+fn civil_from_days(days: i64) -> (i64, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 } as u32;
+    let year = year_of_era + era * 400;
+    (if month <= 2 { year + 1 } else { year }, month)
+}
+
+/// Donations of a clan in the current season.
+///
+/// The counters of the API reset when a season ends and when a member rejoins the clan. A drop of the counters
+/// is attributed to a member who rejoined, unless the season ended since the previous poll.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeasonDonations {
+    clan: ClanTag,
+    season_end: SystemTime,
+    // counters of the members at the previous poll
+    counters: HashMap<PlayerTag, DonationTotals>,
+    totals: HashMap<PlayerTag, DonationTotals>,
+}
+
+impl SeasonDonations {
+    /// Starts tracking a clan, taking the counters of the members as their season totals.
+    ///
+    /// Within a day after the end of a season the counters may not be reset yet, so the ended season is tracked
+    /// and `update` starts the new one as usual, unless most members have no donations yet and the counters
+    /// already look like the new season.
+    ///
+    /// # Arguments
+    /// * `clan` - The tag of the clan the members belong to.
+    /// * `members` - The members of the clan.
+    /// * `now` - The time the members were retrieved.
+    pub fn new(clan: ClanTag, members: &[APIClanMember], now: SystemTime) -> Self {
+        let counters: HashMap<_, _> = members.iter().map(|member| (member.tag.clone(), counters(member))).collect();
+        let fresh = counters.values().filter(|counters| **counters == DonationTotals::default()).count();
+        let season_start = if fresh * 2 > counters.len() { now } else { now.checked_sub(RESET_GRACE).unwrap_or(now) };
+        Self {
+            clan,
+            season_end: season_end(season_start),
+            totals: counters.clone(),
+            counters,
+        }
+    }
+
+    /// Returns the end of the tracked season.
+    pub fn season_end(&self) -> SystemTime {
+        self.season_end
+    }
+
+    /// Returns the totals of every member who donated or received in the season, including members who left.
+    pub fn totals(&self) -> &HashMap<PlayerTag, DonationTotals> {
+        &self.totals
+    }
+
+    /// Returns the totals of a member in the season.
+    pub fn member_totals(&self, tag: &PlayerTag) -> DonationTotals {
+        self.totals.get(tag).copied().unwrap_or_default()
+    }
+
+    /// Attributes the changes of the counters since the previous update to the members.
+    ///
+    /// A new season starts once its end has passed and the counters of most members dropped, or a day after the end.
+    /// Drops of single members are attributed to members who rejoined. Without a drop the counters of the previous
+    /// poll are kept, so only what changed since then is counted for the new season.
+    ///
+    /// # Arguments
+    /// * `members` - The members of the clan.
+    /// * `now` - The time the members were retrieved.
+    pub fn update(&mut self, members: &[APIClanMember], now: SystemTime) -> Vec<DonationEvent> {
+        let mut events = Vec::new();
+        if now >= self.season_end {
+            // only members with counters at the previous poll can show a reset
+            let compared: Vec<_> = members
+                .iter()
+                .filter_map(|member| Some((*self.counters.get(&member.tag)?, counters(member))))
+                .filter(|(previous, _)| *previous != DonationTotals::default())
+                .collect();
+            let dropped = compared
+                .iter()
+                .filter(|(previous, current)| current.donated < previous.donated || current.received < previous.received)
+                .count();
+            let reset = dropped * 2 > compared.len();
+            if reset || now >= self.season_end + RESET_GRACE {
+                events.push(DonationEvent::SeasonEnded {
+                    clan: self.clan.clone(),
+                    totals: std::mem::take(&mut self.totals),
+                });
+                if reset {
+                    self.counters.clear();
+                }
+                self.season_end = season_end(now);
+            }
+        }
+
+        let mut current_counters = HashMap::new();
+        for member in members {
+            let previous = self.counters.get(&member.tag).copied().unwrap_or_default();
+            let current = counters(member);
+            // counters of members who join start at zero
+            let delta = |previous: i64, current: i64| if current < previous { current } else { current - previous };
+            let donated = delta(previous.donated, current.donated);
+            let received = delta(previous.received, current.received);
+            let totals = self.totals.entry(member.tag.clone()).or_default();
+            totals.donated += donated;
+            totals.received += received;
+            if donated > 0 {
+                events.push(DonationEvent::Donated {
+                    clan: self.clan.clone(),
+                    member: member.clone(),
+                    amount: donated,
+                    totals: *totals,
+                });
+            }
+            if received > 0 {
+                events.push(DonationEvent::Received {
+                    clan: self.clan.clone(),
+                    member: member.clone(),
+                    amount: received,
+                    totals: *totals,
+                });
+            }
+            current_counters.insert(member.tag.clone(), current);
+        }
+        self.counters = current_counters;
+        events
+    }
+}
+
+fn counters(member: &APIClanMember) -> DonationTotals {
+    DonationTotals {
+        donated: member.donations,
+        received: member.donations_received,
+    }
+}
+
+/// Polls the member lists of clans and emits `DonationEvent`s.
+///
+/// The first poll of a clan only records its members.
+#[derive(Debug)]
+pub struct DonationTracker {
+    clans: Vec<ClanTag>,
+    interval: Duration,
+    seasons: HashMap<ClanTag, SeasonDonations>,
+}
+
+impl DonationTracker {
+    /// Returns the donations of a clan in the current season, if the clan was polled.
+    pub fn season(&self, clan: &ClanTag) -> Option<&SeasonDonations> {
+        self.seasons.get(clan)
+    }
+
+    /// Retrieves the members of every clan once and returns the donations since the previous poll.
+    ///
+    /// # Arguments
+    /// * `rest_manager` - The `RestManager` used for the requests.
+    ///
+    /// # Returns
+    /// Events of all clans, followed by the errors of the clans that could not be retrieved.
+    pub async fn poll(&mut self, rest_manager: &RestManager) -> Vec<Result<DonationEvent>> {
+        let fetch = |clan| async move { rest_manager.clan_members_paginated(clan)?.collect_all().await };
+        poll_each(&self.clans, fetch, |clan, members| {
+            let now = SystemTime::now();
+            match self.seasons.get_mut(clan) {
+                Some(season) => season.update(&members, now),
+                None => {
+                    self.seasons.insert(clan.clone(), SeasonDonations::new(clan.clone(), &members, now));
+                    Vec::new()
+                }
+            }
+        })
+        .await
+    }
+}
+
+/// Builder for `DonationTracker`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DonationTrackerBuilder {
+    targets: PollTargets,
+}
+
+impl DonationTrackerBuilder {
+    /// Returns the `DonationTracker` that uses `DonationTrackerBuilder` configuration.
+    ///
+    /// # Returns
+    /// `Result` containing a `DonationTracker` if successful; if a tag is invalid or the interval is zero, it contains an `Error`.
+    pub fn build(self) -> Result<DonationTracker> {
+        Ok(DonationTracker {
+            clans: self.targets.tags()?,
+            interval: self.targets.interval()?,
+            seasons: HashMap::new(),
+        })
+    }
+}
+
+impl_poller!(DonationTracker, DonationTrackerBuilder, DonationEvent, clan, clans);
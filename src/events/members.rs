@@ -0,0 +1,164 @@
+use crate::api::clans::models::{APIClanMember, APIClanMemberRole};
+use crate::api::common::tags::{ClanTag, PlayerTag};
+use crate::api::rest_manager::RestManager;
+use crate::errors::Result;
+use crate::events::poller::{impl_poller, poll_each, PollTargets};
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+/// Change of the member list of a clan between two polls.
+///
+/// `member` is the member as of the latest poll, or as last seen for `MemberLeft`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MemberEvent {
+    MemberJoined {
+        clan: ClanTag,
+        member: APIClanMember,
+    },
+    MemberLeft {
+        clan: ClanTag,
+        member: APIClanMember,
+    },
+    RoleChanged {
+        clan: ClanTag,
+        member: APIClanMember,
+        from: APIClanMemberRole,
+        to: APIClanMemberRole,
+    },
+    NameChanged {
+        clan: ClanTag,
+        member: APIClanMember,
+        from: String,
+        to: String,
+    },
+    TownHallUpgraded {
+        clan: ClanTag,
+        member: APIClanMember,
+        from: i64,
+        to: i64,
+    },
+}
+
+impl MemberEvent {
+    /// Returns the tag of the clan the event happened in.
+    pub fn clan(&self) -> &ClanTag {
+        match self {
+            MemberEvent::MemberJoined { clan, .. }
+            | MemberEvent::MemberLeft { clan, .. }
+            | MemberEvent::RoleChanged { clan, .. }
+            | MemberEvent::NameChanged { clan, .. }
+            | MemberEvent::TownHallUpgraded { clan, .. } => clan,
+        }
+    }
+
+    /// Returns the member the event is about.
+    pub fn member(&self) -> &APIClanMember {
+        match self {
+            MemberEvent::MemberJoined { member, .. }
+            | MemberEvent::MemberLeft { member, .. }
+            | MemberEvent::RoleChanged { member, .. }
+            | MemberEvent::NameChanged { member, .. }
+            | MemberEvent::TownHallUpgraded { member, .. } => member,
+        }
+    }
+}
+
+/// Compares two member lists of a clan.
+///
+/// Events of members still in the clan follow the order of `current`, members who left come last.
+///
+/// # Arguments
+/// * `clan` - The tag of the clan the members belong to.
+/// * `previous` - The members at the previous poll.
+/// * `current` - The members at the latest poll.
+pub fn diff_members(clan: &ClanTag, previous: &[APIClanMember], current: &[APIClanMember]) -> Vec<MemberEvent> {
+    let previous_members: HashMap<&PlayerTag, &APIClanMember> = previous.iter().map(|member| (&member.tag, member)).collect();
+    let current_tags: HashSet<&PlayerTag> = current.iter().map(|member| &member.tag).collect();
+    let mut events = Vec::new();
+    for member in current {
+        let Some(old) = previous_members.get(&member.tag) else {
+            events.push(MemberEvent::MemberJoined { clan: clan.clone(), member: member.clone() });
+            continue;
+        };
+        if old.name != member.name {
+            events.push(MemberEvent::NameChanged {
+                clan: clan.clone(),
+                member: member.clone(),
+                from: old.name.clone(),
+                to: member.name.clone(),
+            });
+        }
+        if old.role != member.role {
+            events.push(MemberEvent::RoleChanged {
+                clan: clan.clone(),
+                member: member.clone(),
+                from: old.role.clone(),
+                to: member.role.clone(),
+            });
+        }
+        if member.town_hall_level > old.town_hall_level {
+            events.push(MemberEvent::TownHallUpgraded {
+                clan: clan.clone(),
+                member: member.clone(),
+                from: old.town_hall_level,
+                to: member.town_hall_level,
+            });
+        }
+    }
+    for member in previous.iter().filter(|member| !current_tags.contains(&member.tag)) {
+        events.push(MemberEvent::MemberLeft { clan: clan.clone(), member: member.clone() });
+    }
+    events
+}
+
+/// Polls the member lists of clans and emits `MemberEvent`s.
+///
+/// The first poll of a clan only records its members.
+#[derive(Debug)]
+pub struct MemberPoller {
+    clans: Vec<ClanTag>,
+    interval: Duration,
+    // members of every clan at the previous poll
+    members: HashMap<ClanTag, Vec<APIClanMember>>,
+}
+
+impl MemberPoller {
+    /// Retrieves the members of every clan once and returns the changes since the previous poll.
+    ///
+    /// # Arguments
+    /// * `rest_manager` - The `RestManager` used for the requests.
+    ///
+    /// # Returns
+    /// Events of all clans, followed by the errors of the clans that could not be retrieved.
+    pub async fn poll(&mut self, rest_manager: &RestManager) -> Vec<Result<MemberEvent>> {
+        let fetch = |clan| async move { rest_manager.clan_members_paginated(clan)?.collect_all().await };
+        poll_each(&self.clans, fetch, |clan, members| {
+            let events = self.members.get(clan).map(|previous| diff_members(clan, previous, &members)).unwrap_or_default();
+            self.members.insert(clan.clone(), members);
+            events
+        })
+        .await
+    }
+}
+
+/// Builder for `MemberPoller`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MemberPollerBuilder {
+    targets: PollTargets,
+}
+
+impl MemberPollerBuilder {
+    /// Returns the `MemberPoller` that uses `MemberPollerBuilder` configuration.
+    ///
+    /// # Returns
+    /// `Result` containing a `MemberPoller` if successful; if a tag is invalid or the interval is zero, it contains an `Error`.
+    pub fn build(self) -> Result<MemberPoller> {
+        Ok(MemberPoller {
+            clans: self.targets.tags()?,
+            interval: self.targets.interval()?,
+            members: HashMap::new(),
+        })
+    }
+}
+
+impl_poller!(MemberPoller, MemberPollerBuilder, MemberEvent, clan, clans);
@@ -0,0 +1,6 @@
+pub mod clan;
+pub mod donations;
+pub mod members;
+pub mod players;
+pub mod poller;
+pub mod war;
@@ -0,0 +1,200 @@
+use crate::api::common::tags::PlayerTag;
+use crate::api::players::models::{APIPlayer, APIPlayerAchievementProgress, APIPlayerItemLevel, APIVillageType};
+use crate::api::rest_manager::RestManager;
+use crate::errors::Result;
+use crate::events::poller::{impl_poller, poll_each, PollTargets};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Kind of an upgradable item of a player.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ItemKind {
+    Troop,
+    Hero,
+    Spell,
+    HeroEquipment,
+}
+
+/// Progress of a player between two polls.
+///
+/// `player` is the player as of the latest poll.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlayerEvent {
+    /// `from` is 0 for an item that was unlocked.
+    ItemUpgraded {
+        player: APIPlayer,
+        kind: ItemKind,
+        name: String,
+        village: APIVillageType,
+        from: i64,
+        to: i64,
+    },
+    SuperTroopActivated {
+        player: APIPlayer,
+        name: String,
+    },
+    AchievementStarGained {
+        player: APIPlayer,
+        achievement: APIPlayerAchievementProgress,
+        from: i64,
+        to: i64,
+    },
+    TownHallUpgraded {
+        player: APIPlayer,
+        from: i64,
+        to: i64,
+    },
+    TownHallWeaponUpgraded {
+        player: APIPlayer,
+        from: i64,
+        to: i64,
+    },
+    BuilderHallUpgraded {
+        player: APIPlayer,
+        from: i64,
+        to: i64,
+    },
+}
+
+impl PlayerEvent {
+    /// Returns the player the event is about.
+    pub fn player(&self) -> &APIPlayer {
+        match self {
+            PlayerEvent::ItemUpgraded { player, .. }
+            | PlayerEvent::SuperTroopActivated { player, .. }
+            | PlayerEvent::AchievementStarGained { player, .. }
+            | PlayerEvent::TownHallUpgraded { player, .. }
+            | PlayerEvent::TownHallWeaponUpgraded { player, .. }
+            | PlayerEvent::BuilderHallUpgraded { player, .. } => player,
+        }
+    }
+}
+
+/// Compares two snapshots of a player.
+///
+/// Items and achievements are matched by name and village. Events of the halls come first, followed by troops,
+/// heroes, spells, hero equipment and achievements in the order of `current`.
+///
+/// # Arguments
+/// * `previous` - The player at the previous poll.
+/// * `current` - The player at the latest poll.
+pub fn diff_player(previous: &APIPlayer, current: &APIPlayer) -> Vec<PlayerEvent> {
+    let mut events = Vec::new();
+    if current.town_hall_level > previous.town_hall_level {
+        events.push(PlayerEvent::TownHallUpgraded {
+            player: current.clone(),
+            from: previous.town_hall_level,
+            to: current.town_hall_level,
+        });
+    }
+    if current.town_hall_weapon_level > previous.town_hall_weapon_level {
+        events.push(PlayerEvent::TownHallWeaponUpgraded {
+            player: current.clone(),
+            from: previous.town_hall_weapon_level,
+            to: current.town_hall_weapon_level,
+        });
+    }
+    if current.builder_hall_level > previous.builder_hall_level {
+        events.push(PlayerEvent::BuilderHallUpgraded {
+            player: current.clone(),
+            from: previous.builder_hall_level,
+            to: current.builder_hall_level,
+        });
+    }
+
+    let items = [
+        (ItemKind::Troop, &previous.troops, &current.troops),
+        (ItemKind::Hero, &previous.heroes, &current.heroes),
+        (ItemKind::Spell, &previous.spells, &current.spells),
+        (ItemKind::HeroEquipment, &previous.hero_equipment, &current.hero_equipment),
+    ];
+    for (kind, previous_items, current_items) in items {
+        let previous_items: HashMap<_, &APIPlayerItemLevel> = previous_items.iter().map(|item| ((&item.name, &item.village), item)).collect();
+        for item in current_items {
+            let old = previous_items.get(&(&item.name, &item.village));
+            let from = old.map_or(0, |old| old.level);
+            if item.level > from {
+                events.push(PlayerEvent::ItemUpgraded {
+                    player: current.clone(),
+                    kind,
+                    name: item.name.clone(),
+                    village: item.village.clone(),
+                    from,
+                    to: item.level,
+                });
+            }
+            let was_active = old.is_some_and(|old| old.super_troop_is_active == Some(true));
+            if item.super_troop_is_active == Some(true) && !was_active {
+                events.push(PlayerEvent::SuperTroopActivated {
+                    player: current.clone(),
+                    name: item.name.clone(),
+                });
+            }
+        }
+    }
+
+    let previous_achievements: HashMap<_, i64> = previous.achievements.iter().map(|achievement| ((&achievement.name, &achievement.village), achievement.stars)).collect();
+    for achievement in &current.achievements {
+        let from = previous_achievements.get(&(&achievement.name, &achievement.village)).copied().unwrap_or_default();
+        if achievement.stars > from {
+            events.push(PlayerEvent::AchievementStarGained {
+                player: current.clone(),
+                achievement: achievement.clone(),
+                from,
+                to: achievement.stars,
+            });
+        }
+    }
+    events
+}
+
+/// Polls players and emits `PlayerEvent`s.
+///
+/// The first poll of a player only records it.
+#[derive(Debug)]
+pub struct PlayerWatcher {
+    players: Vec<PlayerTag>,
+    interval: Duration,
+    // every player at the previous poll
+    snapshots: HashMap<PlayerTag, APIPlayer>,
+}
+
+impl PlayerWatcher {
+    /// Retrieves every player once and returns the progress since the previous poll.
+    ///
+    /// # Arguments
+    /// * `rest_manager` - The `RestManager` used for the requests.
+    ///
+    /// # Returns
+    /// Events of all players, followed by the errors of the players that could not be retrieved.
+    pub async fn poll(&mut self, rest_manager: &RestManager) -> Vec<Result<PlayerEvent>> {
+        poll_each(&self.players, |tag| rest_manager.player(tag), |tag, player| {
+            let events = self.snapshots.get(tag).map(|previous| diff_player(previous, &player)).unwrap_or_default();
+            self.snapshots.insert(tag.clone(), player);
+            events
+        })
+        .await
+    }
+}
+
+/// Builder for `PlayerWatcher`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PlayerWatcherBuilder {
+    targets: PollTargets,
+}
+
+impl PlayerWatcherBuilder {
+    /// Returns the `PlayerWatcher` that uses `PlayerWatcherBuilder` configuration.
+    ///
+    /// # Returns
+    /// `Result` containing a `PlayerWatcher` if successful; if a tag is invalid or the interval is zero, it contains an `Error`.
+    pub fn build(self) -> Result<PlayerWatcher> {
+        Ok(PlayerWatcher {
+            players: self.targets.tags()?,
+            interval: self.targets.interval()?,
+            snapshots: HashMap::new(),
+        })
+    }
+}
+
+impl_poller!(PlayerWatcher, PlayerWatcherBuilder, PlayerEvent, player, players);
@@ -0,0 +1,212 @@
+use crate::api::rest_manager::RestManager;
+use crate::errors::{Error, Result};
+use futures::Stream;
+use std::future::Future;
+use std::str::FromStr;
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::JoinHandle;
+
+/// Number of events kept for a slow receiver before polling waits for it.
+const EVENT_BUFFER: usize = 256;
+
+/// Snapshots of the API that are compared between polls to produce events.
+pub(crate) trait EventSource: Send + 'static {
+    type Event: Send + 'static;
+
+    /// Fetches the current snapshots and returns the events since the previous poll.
+    fn poll(&mut self, rest_manager: &RestManager) -> impl Future<Output = Vec<Result<Self::Event>>> + Send;
+}
+
+/// Receives the events of a poller running in the background.
+///
+/// Errors of a poll are delivered as well; polling continues with the next interval.
+/// The poller stops when the receiver is dropped or `EventReceiver::shutdown` is called.
+#[derive(Debug)]
+pub struct EventReceiver<E> {
+    events: mpsc::Receiver<Result<E>>,
+    // dropping it stops the poller
+    shutdown: oneshot::Sender<()>,
+    task: JoinHandle<()>,
+}
+
+impl<E: Send + 'static> EventReceiver<E> {
+    /// Waits for the next event; `None` once the poller has stopped.
+    pub async fn recv(&mut self) -> Option<Result<E>> {
+        self.events.recv().await
+    }
+
+    /// Stops the poller and waits until its task has finished; events not received yet are dropped.
+    pub async fn shutdown(self) {
+        let _ = self.shutdown.send(());
+        let _ = self.task.await;
+    }
+
+    /// Converts the receiver into a `Stream` of events, the poller stops when it is dropped.
+    pub fn into_stream(self) -> impl Stream<Item = Result<E>> + Send + 'static {
+        futures::stream::unfold(self, |mut receiver| async move {
+            receiver.recv().await.map(|event| (event, receiver))
+        })
+    }
+}
+
+/// Tags and interval configured in the builder of a poller.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PollTargets {
+    tags: Vec<String>,
+    interval: Duration,
+}
+
+impl Default for PollTargets {
+    fn default() -> Self {
+        Self {
+            tags: Vec::new(),
+            interval: Duration::from_secs(60),
+        }
+    }
+}
+
+impl PollTargets {
+    pub(crate) fn push(&mut self, tag: impl AsRef<str>) {
+        self.tags.push(tag.as_ref().to_string());
+    }
+
+    pub(crate) fn extend<I, S>(&mut self, tags: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.tags.extend(tags.into_iter().map(|tag| tag.as_ref().to_string()));
+    }
+
+    pub(crate) fn set_interval(&mut self, interval: Duration) {
+        self.interval = interval;
+    }
+
+    /// Parses the tags, failing on the first invalid one.
+    pub(crate) fn tags<T: FromStr<Err = Error>>(&self) -> Result<Vec<T>> {
+        self.tags.iter().map(|tag| tag.parse()).collect()
+    }
+
+    /// Returns the interval, failing on zero since `tokio::time::interval` panics on it.
+    pub(crate) fn interval(&self) -> Result<Duration> {
+        if self.interval.is_zero() { Err(Error::InvalidInterval) } else { Ok(self.interval) }
+    }
+}
+
+/// Implements what every poller and its builder have in common: `builder`, `spawn`, `EventSource`,
+/// and `new`, the methods adding tags and `interval` of the builder.
+///
+/// The poller needs an `interval` field, an inherent `poll` method, and the builder a `targets: PollTargets` field.
+macro_rules! impl_poller {
+    ($poller:ident, $builder:ident, $event:ident, $tag:ident, $tags:ident) => {
+        impl $poller {
+            #[doc = concat!("Creates a `", stringify!($builder), "` to configure `", stringify!($poller), "`.")]
+            ///
+            #[doc = concat!("This is the same as `", stringify!($builder), "::new()`.")]
+            pub fn builder() -> $builder {
+                $builder::new()
+            }
+
+            /// Starts polling every configured interval on a tokio task.
+            ///
+            /// # Arguments
+            /// * `rest_manager` - The `RestManager` used for the requests.
+            pub fn spawn(self, rest_manager: &$crate::api::rest_manager::RestManager) -> $crate::events::poller::EventReceiver<$event> {
+                let interval = self.interval;
+                $crate::events::poller::spawn(self, rest_manager, interval)
+            }
+        }
+
+        impl $crate::events::poller::EventSource for $poller {
+            type Event = $event;
+
+            async fn poll(&mut self, rest_manager: &$crate::api::rest_manager::RestManager) -> Vec<$crate::errors::Result<$event>> {
+                $poller::poll(self, rest_manager).await
+            }
+        }
+
+        impl $builder {
+            #[doc = concat!("Constructs a new `", stringify!($builder), "`.")]
+            ///
+            #[doc = concat!("This is the same as `", stringify!($poller), "::builder()`.")]
+            pub fn new() -> Self {
+                Self::default()
+            }
+
+            #[doc = concat!("Adds a ", stringify!($tag), " to poll.")]
+            pub fn $tag(mut self, tag: impl AsRef<str>) -> Self {
+                self.targets.push(tag);
+                self
+            }
+
+            #[doc = concat!("Adds several ", stringify!($tags), " to poll.")]
+            pub fn $tags<I, S>(mut self, tags: I) -> Self
+            where
+                I: IntoIterator<Item = S>,
+                S: AsRef<str>,
+            {
+                self.targets.extend(tags);
+                self
+            }
+
+            /// Sets the time between polls, one minute by default.
+            pub fn interval(mut self, interval: ::std::time::Duration) -> Self {
+                self.targets.set_interval(interval);
+                self
+            }
+        }
+    };
+}
+
+pub(crate) use impl_poller;
+
+/// Retrieves every target in turn and passes it to `update`, which returns the events since the previous poll.
+///
+/// # Returns
+/// Events of all targets, followed by the errors of the targets that could not be retrieved.
+pub(crate) async fn poll_each<'a, T, V, E, F>(targets: &'a [T], fetch: impl Fn(&'a T) -> F, mut update: impl FnMut(&T, V) -> Vec<E>) -> Vec<Result<E>>
+where
+    F: Future<Output = Result<V>>,
+{
+    let mut events = Vec::new();
+    let mut errors = Vec::new();
+    for target in targets {
+        match fetch(target).await {
+            Ok(value) => events.extend(update(target, value).into_iter().map(Ok)),
+            Err(err) => errors.push(Err(err)),
+        }
+    }
+    events.extend(errors);
+    events
+}
+
+/// Polls the source every `interval` on a tokio task, starting immediately.
+pub(crate) fn spawn<S: EventSource>(mut source: S, rest_manager: &RestManager, interval: Duration) -> EventReceiver<S::Event> {
+    let rest_manager = rest_manager.clone();
+    let (sender, events) = mpsc::channel(EVENT_BUFFER);
+    let (shutdown, mut stopped) = oneshot::channel();
+    let task = tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        // a slow poll delays the next one instead of causing a burst
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            let iteration = async {
+                ticker.tick().await;
+                for event in source.poll(&rest_manager).await {
+                    sender.send(event).await.ok()?;
+                }
+                Some(())
+            };
+            let running = tokio::select! {
+                _ = &mut stopped => false,
+                // None if the receiver was dropped
+                sent = iteration => sent.is_some(),
+            };
+            if !running {
+                break;
+            }
+        }
+    });
+    EventReceiver { events, shutdown, task }
+}
@@ -0,0 +1,440 @@
+use crate::api::clans::models::{APIClanWar, APIClanWarAttack, APIClanWarMember, APIWarClan, APIWarResult, APIWarState};
+use crate::api::common::tags::{ClanTag, PlayerTag, WarTag};
+use crate::api::common::timestamp::APITimestamp;
+use crate::api::rest_manager::RestManager;
+use crate::errors::Result;
+use crate::events::poller::{impl_poller, PollTargets};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+/// Identifies a war of a clan across polls and restarts.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct WarId {
+    /// The watched clan.
+    pub clan: ClanTag,
+    pub opponent: ClanTag,
+    // distinguishes consecutive wars against the same opponent
+    pub preparation_start_time: APITimestamp,
+    // None for regular wars
+    pub war_tag: Option<WarTag>,
+}
+
+impl WarId {
+    /// Identifies a war from the side of `war.clan`; None if it has no opponent yet.
+    pub fn new(war: &APIClanWar, war_tag: Option<&WarTag>) -> Option<Self> {
+        Some(Self {
+            clan: war.clan.tag.clone()?,
+            opponent: war.opponent.tag.clone()?,
+            preparation_start_time: war.preparation_start_time.clone()?,
+            war_tag: war_tag.cloned(),
+        })
+    }
+}
+
+/// What was already reported about a war.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WarProgress {
+    pub war: WarId,
+    pub state: APIWarState,
+    /// Highest `APIClanWarAttack::order` reported, 0 before the first attack.
+    pub last_attack_order: i64,
+}
+
+impl WarProgress {
+    /// Creates the progress of a war nothing was reported about.
+    pub fn new(war: WarId) -> Self {
+        Self {
+            war,
+            state: APIWarState::NotInWar,
+            last_attack_order: 0,
+        }
+    }
+}
+
+/// Result of one side of a war.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WarScore {
+    pub stars: i64,
+    pub destruction_percentage: Decimal,
+    pub attacks: Option<i64>,
+}
+
+impl From<&APIWarClan> for WarScore {
+    fn from(clan: &APIWarClan) -> Self {
+        Self {
+            stars: clan.stars,
+            destruction_percentage: clan.destruction_percentage,
+            attacks: clan.attacks,
+        }
+    }
+}
+
+/// Change of a war between two polls, always from the side of the watched clan.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WarEvent {
+    WarStateChanged {
+        war: WarId,
+        from: APIWarState,
+        to: APIWarState,
+    },
+    /// Attack of a member of the watched clan.
+    AttackMade {
+        war: WarId,
+        attacker: APIClanWarMember,
+        attack: APIClanWarAttack,
+    },
+    /// Attack of the opponent on a member of the watched clan.
+    DefenseReceived {
+        war: WarId,
+        defender: APIClanWarMember,
+        attack: APIClanWarAttack,
+    },
+    /// Attack of the opponent that became the best one on a member of the watched clan.
+    NewBestOpponentAttack {
+        war: WarId,
+        defender: APIClanWarMember,
+        attack: APIClanWarAttack,
+    },
+    /// Built from the latest poll of the war if it disappeared before it was seen ended.
+    WarEnded {
+        war: WarId,
+        clan: WarScore,
+        opponent: WarScore,
+        result: APIWarResult,
+    },
+}
+
+impl WarEvent {
+    /// Returns the war the event happened in.
+    pub fn war(&self) -> &WarId {
+        match self {
+            WarEvent::WarStateChanged { war, .. }
+            | WarEvent::AttackMade { war, .. }
+            | WarEvent::DefenseReceived { war, .. }
+            | WarEvent::NewBestOpponentAttack { war, .. }
+            | WarEvent::WarEnded { war, .. } => war,
+        }
+    }
+
+    /// Returns the attack the event is about, if any.
+    pub fn attack(&self) -> Option<&APIClanWarAttack> {
+        match self {
+            WarEvent::AttackMade { attack, .. }
+            | WarEvent::DefenseReceived { attack, .. }
+            | WarEvent::NewBestOpponentAttack { attack, .. } => Some(attack),
+            WarEvent::WarStateChanged { .. } | WarEvent::WarEnded { .. } => None,
+        }
+    }
+}
+
+/// Compares a war with what was reported about it and records the new events in `progress`.
+///
+/// Attacks are reported in their order, each new best attack of the opponent right after the attack itself.
+///
+/// # Arguments
+/// * `progress` - What was reported about the war so far.
+/// * `war` - The war at the latest poll, from the side of the watched clan.
+pub fn diff_war(progress: &mut WarProgress, war: &APIClanWar) -> Vec<WarEvent> {
+    let id = &progress.war;
+    let mut events = Vec::new();
+    if progress.state != war.state {
+        events.push(WarEvent::WarStateChanged {
+            war: id.clone(),
+            from: progress.state.clone(),
+            to: war.state.clone(),
+        });
+    }
+
+    let own_members = war.clan.members.as_deref().unwrap_or_default();
+    let opponent_members = war.opponent.members.as_deref().unwrap_or_default();
+    let mut attacks: Vec<_> = new_attacks(own_members, progress.last_attack_order)
+        .map(|attack| (attack, true))
+        .chain(new_attacks(opponent_members, progress.last_attack_order).map(|attack| (attack, false)))
+        .collect();
+    attacks.sort_by_key(|(attack, _)| attack.order);
+
+    let own_member = |tag: &PlayerTag| own_members.iter().find(|member| &member.tag == tag);
+    for (attack, own) in attacks {
+        if own {
+            if let Some(attacker) = own_member(&attack.attacker_tag) {
+                events.push(WarEvent::AttackMade { war: id.clone(), attacker: attacker.clone(), attack: attack.clone() });
+            }
+        } else if let Some(defender) = own_member(&attack.defender_tag) {
+            events.push(WarEvent::DefenseReceived { war: id.clone(), defender: defender.clone(), attack: attack.clone() });
+            if defender.best_opponent_attack.as_ref() == Some(attack) {
+                events.push(WarEvent::NewBestOpponentAttack { war: id.clone(), defender: defender.clone(), attack: attack.clone() });
+            }
+        }
+        progress.last_attack_order = progress.last_attack_order.max(attack.order);
+    }
+
+    if war.state == APIWarState::WarEnded && progress.state != APIWarState::WarEnded {
+        events.push(WarEvent::WarEnded {
+            war: id.clone(),
+            clan: (&war.clan).into(),
+            opponent: (&war.opponent).into(),
+            result: war_result(&war.clan, &war.opponent),
+        });
+    }
+    progress.state = war.state.clone();
+    events
+}
+
+/// Returns the attacks of the members made after the attack with the given order.
+fn new_attacks(members: &[APIClanWarMember], last_order: i64) -> impl Iterator<Item = &APIClanWarAttack> {
+    members
+        .iter()
+        .flat_map(|member| member.attacks.iter().flatten())
+        .filter(move |attack| attack.order > last_order)
+}
+
+/// Decides the war by stars, then by destruction.
+fn war_result(clan: &APIWarClan, opponent: &APIWarClan) -> APIWarResult {
+    match (clan.stars, clan.destruction_percentage).cmp(&(opponent.stars, opponent.destruction_percentage)) {
+        Ordering::Greater => APIWarResult::Win,
+        Ordering::Less => APIWarResult::Lose,
+        Ordering::Equal => APIWarResult::Tie,
+    }
+}
+
+/// Reports the end of a war that is no longer returned for its clan although it was not seen ended.
+///
+/// League wars are requested once more to report their final attacks; otherwise the end is taken from the
+/// latest poll of the war, and nothing is reported for wars that were not polled since a resume.
+async fn end_disappeared_war(rest_manager: &RestManager, mut progress: WarProgress, snapshot: Option<APIClanWar>) -> Vec<WarEvent> {
+    if let Some(war_tag) = &progress.war.war_tag
+        && let Ok(war) = rest_manager.clan_war_league_war(war_tag).await
+        && let Some(war) = oriented(war, &progress.war.clan)
+        && war.state == APIWarState::WarEnded
+    {
+        return diff_war(&mut progress, &war);
+    }
+    snapshot
+        .map(|war| WarEvent::WarEnded {
+            war: progress.war,
+            clan: (&war.clan).into(),
+            opponent: (&war.opponent).into(),
+            result: war_result(&war.clan, &war.opponent),
+        })
+        .into_iter()
+        .collect()
+}
+
+/// Returns the war from the side of `clan`; None if the clan does not take part in it.
+fn oriented(mut war: APIClanWar, clan: &ClanTag) -> Option<APIClanWar> {
+    if war.opponent.tag.as_ref() == Some(clan) {
+        std::mem::swap(&mut war.clan, &mut war.opponent);
+    }
+    (war.clan.tag.as_ref() == Some(clan)).then_some(war)
+}
+
+/// Handled war events, saved to resume a `WarWatcher` without reporting them again.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct WarCheckpoint {
+    // clans polled before, wars appearing for them later are reported from the start
+    pub clans: Vec<ClanTag>,
+    pub wars: Vec<WarProgress>,
+}
+
+impl WarCheckpoint {
+    /// Records an event as handled.
+    pub fn record(&mut self, event: &WarEvent) {
+        let war = event.war();
+        if !self.clans.contains(&war.clan) {
+            self.clans.push(war.clan.clone());
+        }
+        let index = match self.wars.iter().position(|progress| &progress.war == war) {
+            Some(index) => index,
+            None => {
+                self.wars.push(WarProgress::new(war.clone()));
+                self.wars.len() - 1
+            }
+        };
+        let progress = &mut self.wars[index];
+        match event {
+            WarEvent::WarStateChanged { to, .. } => progress.state = to.clone(),
+            WarEvent::WarEnded { .. } => progress.state = APIWarState::WarEnded,
+            WarEvent::AttackMade { attack, .. } | WarEvent::DefenseReceived { attack, .. } | WarEvent::NewBestOpponentAttack { attack, .. } => {
+                progress.last_attack_order = progress.last_attack_order.max(attack.order);
+            }
+        }
+    }
+}
+
+/// Polls the current wars of clans, including Clan War League wars, and emits `WarEvent`s.
+///
+/// The first poll of a clan only records its wars, unless it was resumed from a `WarCheckpoint`.
+#[derive(Debug)]
+pub struct WarWatcher {
+    clans: Vec<ClanTag>,
+    interval: Duration,
+    league_wars: bool,
+    // clans polled at least once
+    polled: HashSet<ClanTag>,
+    wars: HashMap<WarId, WarProgress>,
+    // every war at its latest poll, to report the end of wars that disappear before they are seen ended
+    snapshots: HashMap<WarId, APIClanWar>,
+    // league wars between other clans of the group of every clan, they are not requested again
+    foreign_wars: HashMap<ClanTag, HashSet<WarTag>>,
+}
+
+impl WarWatcher {
+    /// Returns what was reported so far, see `WarWatcherBuilder::resume`.
+    pub fn checkpoint(&self) -> WarCheckpoint {
+        WarCheckpoint {
+            clans: self.clans.iter().filter(|clan| self.polled.contains(*clan)).cloned().collect(),
+            wars: self.wars.values().cloned().collect(),
+        }
+    }
+
+    /// Retrieves the wars of every clan once and returns the changes since the previous poll.
+    ///
+    /// # Arguments
+    /// * `rest_manager` - The `RestManager` used for the requests.
+    ///
+    /// # Returns
+    /// Events of all clans, followed by the errors of the clans whose wars could not be retrieved.
+    pub async fn poll(&mut self, rest_manager: &RestManager) -> Vec<Result<WarEvent>> {
+        let mut events = Vec::new();
+        let mut errors = Vec::new();
+        for clan in self.clans.clone() {
+            let wars = match self.fetch_wars(rest_manager, &clan).await {
+                Ok(wars) => wars,
+                Err(err) => {
+                    errors.push(Err(err));
+                    continue;
+                }
+            };
+            let first_poll = self.polled.insert(clan.clone());
+            let mut seen = HashSet::new();
+            for (id, war) in wars {
+                seen.insert(id.clone());
+                let Some(war) = war else {
+                    continue;
+                };
+                let progress = self.wars.entry(id.clone()).or_insert_with(|| WarProgress::new(id.clone()));
+                let war_events = diff_war(progress, &war);
+                if !first_poll {
+                    events.extend(war_events.into_iter().map(Ok));
+                }
+                self.snapshots.insert(id, war);
+            }
+            // wars that are no longer returned by the API are forgotten once their end is reported
+            let gone: Vec<_> = self.wars.keys().filter(|id| id.clan == clan && !seen.contains(*id)).cloned().collect();
+            for id in gone {
+                let progress = self.wars.remove(&id).expect("Disappeared war should have progress");
+                let snapshot = self.snapshots.remove(&id);
+                if !first_poll && progress.state != APIWarState::WarEnded {
+                    events.extend(end_disappeared_war(rest_manager, progress, snapshot).await.into_iter().map(Ok));
+                }
+            }
+        }
+        events.extend(errors);
+        events
+    }
+
+    /// Returns the wars of the clan, None for league wars that ended before and were not requested.
+    async fn fetch_wars(&mut self, rest_manager: &RestManager, clan: &ClanTag) -> Result<Vec<(WarId, Option<APIClanWar>)>> {
+        let war = rest_manager.current_war(clan).await?;
+        if war.state != APIWarState::NotInWar || !self.league_wars {
+            return Ok(WarId::new(&war, None).map(|id| (id, Some(war))).into_iter().collect());
+        }
+        let group = match rest_manager.clan_war_league_group(clan).await {
+            Ok(group) => group,
+            // the clan does not take part in the league
+            Err(err) if err.is_not_found() => {
+                self.foreign_wars.remove(clan);
+                return Ok(Vec::new());
+            }
+            Err(err) => return Err(err),
+        };
+        // rounds that are not scheduled yet have `#0` as their war tags
+        let war_tags: HashSet<_> = group.rounds.iter().flat_map(|round| &round.war_tags).filter(|tag| **tag != "#0").collect();
+        // wars of previous groups are not returned again
+        let foreign_wars = self.foreign_wars.entry(clan.clone()).or_default();
+        foreign_wars.retain(|war_tag| war_tags.contains(war_tag));
+        let mut wars = Vec::new();
+        for war_tag in group.rounds.iter().flat_map(|round| &round.war_tags).filter(|tag| **tag != "#0") {
+            if self.foreign_wars.get(clan).is_some_and(|foreign_wars| foreign_wars.contains(war_tag)) {
+                continue;
+            }
+            let ended = self
+                .wars
+                .values()
+                .find(|progress| progress.war.war_tag.as_ref() == Some(war_tag) && progress.state == APIWarState::WarEnded);
+            if let Some(progress) = ended {
+                wars.push((progress.war.clone(), None));
+                continue;
+            }
+            let war = rest_manager.clan_war_league_war(war_tag).await?;
+            match oriented(war, clan) {
+                Some(war) => wars.extend(WarId::new(&war, Some(war_tag)).map(|id| (id, Some(war)))),
+                None => {
+                    self.foreign_wars.entry(clan.clone()).or_default().insert(war_tag.clone());
+                }
+            }
+        }
+        Ok(wars)
+    }
+}
+
+/// Builder for `WarWatcher`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WarWatcherBuilder {
+    targets: PollTargets,
+    league_wars: bool,
+    checkpoint: WarCheckpoint,
+}
+
+impl Default for WarWatcherBuilder {
+    fn default() -> Self {
+        Self {
+            targets: PollTargets::default(),
+            league_wars: true,
+            checkpoint: WarCheckpoint::default(),
+        }
+    }
+}
+
+impl WarWatcherBuilder {
+    /// Sets whether Clan War League wars are watched while the clan is not in a regular war, enabled by default.
+    pub fn league_wars(mut self, league_wars: bool) -> Self {
+        self.league_wars = league_wars;
+        self
+    }
+
+    /// Continues from a checkpoint, so events recorded in it are not reported again.
+    pub fn resume(mut self, checkpoint: WarCheckpoint) -> Self {
+        self.checkpoint = checkpoint;
+        self
+    }
+
+    /// Returns the `WarWatcher` that uses `WarWatcherBuilder` configuration.
+    ///
+    /// # Returns
+    /// `Result` containing a `WarWatcher` if successful; if a tag is invalid or the interval is zero, it contains an `Error`.
+    pub fn build(self) -> Result<WarWatcher> {
+        let clans: Vec<ClanTag> = self.targets.tags()?;
+        let polled = self.checkpoint.clans.into_iter().filter(|clan| clans.contains(clan)).collect();
+        let wars = self
+            .checkpoint
+            .wars
+            .into_iter()
+            .map(|progress| (progress.war.clone(), progress))
+            .collect();
+        Ok(WarWatcher {
+            clans,
+            interval: self.targets.interval()?,
+            league_wars: self.league_wars,
+            polled,
+            wars,
+            snapshots: HashMap::new(),
+            foreign_wars: HashMap::new(),
+        })
+    }
+}
+
+impl_poller!(WarWatcher, WarWatcherBuilder, WarEvent, clan, clans);
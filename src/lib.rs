@@ -3,11 +3,21 @@
 //! Rust library for interacting with the Clash of Clans API.
 //!
 //! # Features
-//! TODO: Write features
+//! - `http`: `RestManager` and the HTTP stack, without TLS.
+//! - `rustls` (default): HTTPS through rustls.
+//! - `native-tls`: HTTPS through the TLS library of the platform.
+//! - `clans`, `players`, `leagues`, `locations`, `labels`, `goldpass` (default): models and requests of an API group.
+//! - `models-only`: models of all groups without the HTTP stack, use with `default-features = false`.
+//! - `blocking`: `BlockingRestManager` for synchronous code.
+//! - `events`: pollers that emit events about clans and players, e.g. `events::members::MemberPoller`.
+//! - `chrono`: times of the API as `chrono::DateTime<Utc>`.
+//! - `tracing`, `metrics`: instrumentation of API calls.
 //!
 //! # License
 //! This project is licensed under the MIT License. See the [LICENSE](LICENSE) file for details.
 
 // Public modules
 pub mod errors;
-pub mod api;
\ No newline at end of file
+pub mod api;
+#[cfg(feature = "events")]
+pub mod events;
@@ -0,0 +1,43 @@
+use crate::api::utils::{get_in_memory_config, labels_page};
+use clash_forge::api::blocking::BlockingRestManager;
+use clash_forge::api::transport::{HttpResponse, InMemoryTransport};
+use reqwest::Method;
+use std::sync::Arc;
+
+fn get_blocking_rest_manager(transport: Arc<InMemoryTransport>) -> BlockingRestManager {
+    BlockingRestManager::with_config("test_token", get_in_memory_config(transport)).expect("Test object should be created")
+}
+
+#[test]
+fn blocking_player_test() {
+    let body = std::fs::read(crate::api::utils::get_mock_data_path("players/8VURQOYUJ.json")).expect("Mock data should exist");
+    let transport = Arc::new(InMemoryTransport::new());
+    transport.push_response(Method::GET, "players/%238VURQ0YUJ", HttpResponse::new(200, body));
+    transport.push_response(Method::GET, "players/%23222", HttpResponse::new(404, r#"{"reason":"notFound"}"#));
+
+    let rest_manager = get_blocking_rest_manager(transport);
+    let player = rest_manager.player("#8VURQ0YUJ").expect("Player should be retrieved");
+    assert_eq!(player.tag, "#8VURQ0YUJ");
+
+    let err = rest_manager.player("#222").expect_err("Unknown player should fail");
+    assert!(err.is_not_found(), "Unexpected error: {err}");
+}
+
+#[test]
+fn blocking_paginator_test() {
+    let transport = Arc::new(InMemoryTransport::new());
+    transport.push_response(Method::GET, "labels/players", labels_page(&[1, 2], Some("cursor1")));
+    transport.push_response(Method::GET, "labels/players", labels_page(&[3], None));
+
+    let rest_manager = get_blocking_rest_manager(transport.clone());
+    let labels: Vec<_> = rest_manager
+        .player_labels_paginated()
+        .page_size(2)
+        .map(|label| label.expect("Label should be retrieved").id)
+        .collect();
+    assert_eq!(labels, vec![1, 2, 3]);
+    assert_eq!(transport.requests().len(), 2);
+
+    let err = rest_manager.clan_members_paginated("#INVALID").expect_err("Invalid tag should fail");
+    assert!(matches!(err, clash_forge::errors::Error::InvalidTag(_)), "Unexpected error: {err}");
+}
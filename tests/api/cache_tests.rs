@@ -0,0 +1,96 @@
+use crate::api::utils::get_test_config;
+use clash_forge::api::cache::{Cache, CacheEntry, CacheMode, CacheStats, InMemoryCache};
+use clash_forge::api::rest_manager::{RestManager, RestManagerConfig};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+const GOLDPASS_BODY: &str = r#"{"startTime":"20250101T080000.000Z","endTime":"20250201T080000.000Z"}"#;
+
+fn get_cached_rest_manager(base_url: &str, cache: Arc<InMemoryCache>) -> RestManager {
+    let config = RestManagerConfig {
+        cache: Some(cache),
+        ..get_test_config(base_url)
+    };
+    RestManager::with_config("test_token", config).expect("Test object should be created")
+}
+
+async fn mock_goldpass(server: &mut mockito::Server, cache_control: &str, hits: usize) -> mockito::Mock {
+    server
+        .mock("GET", "/goldpass/seasons/current")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_header("cache-control", cache_control)
+        .with_body(GOLDPASS_BODY)
+        .expect(hits)
+        .create_async()
+        .await
+}
+
+#[tokio::test]
+async fn cache_hit_test() {
+    let mut server = mockito::Server::new_async().await;
+    let url = server.url();
+    let mock = mock_goldpass(&mut server, "public max-age=600", 1).await;
+    let cache = Arc::new(InMemoryCache::new());
+    let rest_manager = get_cached_rest_manager(&url, cache.clone());
+    for _ in 0..3 {
+        let result = rest_manager.goldpass().await;
+        assert!(result.is_ok(), "Goldpass request returned an error: {:#?}", result.err());
+    }
+    mock.assert_async().await;
+    assert_eq!(rest_manager.cache_stats(), CacheStats { hits: 2, misses: 1 });
+    assert_eq!(cache.len(), 1);
+}
+
+#[tokio::test]
+async fn cache_modes_test() {
+    let mut server = mockito::Server::new_async().await;
+    let url = server.url();
+    let mock = mock_goldpass(&mut server, "max-age=600", 3).await;
+    let rest_manager = get_cached_rest_manager(&url, Arc::new(InMemoryCache::new()));
+
+    rest_manager.goldpass().await.expect("Goldpass request should succeed");
+    rest_manager.with_cache_mode(CacheMode::Bypass).goldpass().await.expect("Goldpass request should succeed");
+    rest_manager.with_cache_mode(CacheMode::ForceRefresh).goldpass().await.expect("Goldpass request should succeed");
+    rest_manager.goldpass().await.expect("Goldpass request should succeed");
+
+    mock.assert_async().await;
+    assert_eq!(rest_manager.cache_stats(), CacheStats { hits: 1, misses: 1 });
+}
+
+#[tokio::test]
+async fn cache_without_max_age_test() {
+    let mut server = mockito::Server::new_async().await;
+    let url = server.url();
+    let mock = mock_goldpass(&mut server, "no-cache", 2).await;
+    let cache = Arc::new(InMemoryCache::new());
+    let rest_manager = get_cached_rest_manager(&url, cache.clone());
+    for _ in 0..2 {
+        rest_manager.goldpass().await.expect("Goldpass request should succeed");
+    }
+    mock.assert_async().await;
+    assert!(cache.is_empty(), "Responses without max-age should not be stored");
+}
+
+#[tokio::test]
+async fn in_memory_cache_expiry_test() {
+    let cache = InMemoryCache::new();
+    let expired = CacheEntry {
+        value: serde_json::json!({"expired": true}),
+        expires_at: SystemTime::now() - Duration::from_secs(1),
+    };
+    let fresh = CacheEntry {
+        value: serde_json::json!({"expired": false}),
+        expires_at: SystemTime::now() + Duration::from_secs(60),
+    };
+    cache.insert("expired".to_string(), expired).await;
+    cache.insert("fresh".to_string(), fresh.clone()).await;
+    assert!(cache.get("expired").await.is_some_and(|entry| entry.is_expired()));
+
+    cache.purge_expired();
+    assert!(cache.get("expired").await.is_none(), "Expired entry should be purged");
+    assert_eq!(cache.get("fresh").await, Some(fresh));
+
+    cache.remove("fresh").await;
+    assert!(cache.is_empty());
+}
@@ -4,7 +4,7 @@ use clash_forge::api::clans::search::ClanSearchOptions;
 #[test]
 fn search_correct_query_parameters_test() {
     let search_options = ClanSearchOptions::builder()
-        .name("Test Clan".to_string())
+        .name("Test Clan")
         .war_frequency(APIWarFrequency::Always)
         .location_id(12345)
         .min_members(10)
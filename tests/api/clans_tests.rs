@@ -165,9 +165,9 @@ async fn clan_war_league_war_test() {
     let mut server = mockito::Server::new_async().await;
     let url = server.url();
     let tag_state_list = vec![
-        ("DAY1TAG", "ended"),
-        ("DAY1TAG", "inWar"),
-        ("DAY2TAG", "preparation"),
+        ("8LUYQJ2G9", "ended"),
+        ("8LUYQJ2G9", "inWar"),
+        ("8LUYQP8UC", "preparation"),
     ];
     for (tag, state) in tag_state_list {
         let normalized_tag = normalize_tag(tag);
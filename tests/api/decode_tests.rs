@@ -0,0 +1,104 @@
+use crate::api::utils::{get_in_memory_config, get_mock_json};
+use clash_forge::api::clans::models::{APIClan, APIClanWarLogEntry};
+use clash_forge::api::common::models::APIPagedResponse;
+use clash_forge::api::decode::DecodeMode;
+use clash_forge::api::players::models::APIPlayer;
+use clash_forge::api::rest_manager::{RestManager, RestManagerConfig};
+use clash_forge::api::transport::{HttpResponse, InMemoryTransport};
+use clash_forge::errors::Error;
+use reqwest::Method;
+use std::sync::Arc;
+
+const PLAYER_PATH: &str = "players/%238VURQ0YUJ";
+
+fn get_rest_manager(decode_mode: DecodeMode, player: serde_json::Value) -> RestManager {
+    let transport = Arc::new(InMemoryTransport::new());
+    transport.push_response(Method::GET, PLAYER_PATH, HttpResponse::new(200, player.to_string()));
+    let config = RestManagerConfig {
+        decode_mode,
+        ..get_in_memory_config(transport)
+    };
+    RestManager::with_config("test_token", config).expect("Test object should be created")
+}
+
+/// Player response without `heroEquipment` and `builderBaseLeague` and with a field the model does not know.
+fn get_changed_player() -> serde_json::Value {
+    let mut player = get_mock_json("players/8VURQOYUJ.json");
+    let fields = player.as_object_mut().expect("Player should be an object");
+    fields.remove("heroEquipment");
+    fields.remove("builderBaseLeague");
+    fields.insert("newField".to_string(), 42.into());
+    player["troops"][0]["newTroopField"] = true.into();
+    player
+}
+
+#[tokio::test]
+async fn lenient_decode_test() {
+    let rest_manager = get_rest_manager(DecodeMode::Lenient, get_changed_player());
+    let response = rest_manager.get_raw::<APIPlayer>(PLAYER_PATH, None).await.expect("Player should be decoded leniently");
+    assert!(response.value.hero_equipment.is_empty());
+    assert!(response.value.builder_base_league.is_none());
+
+    let diagnostics = &response.diagnostics;
+    assert_eq!(diagnostics.endpoint, "players/{tag}");
+    assert_eq!(diagnostics.missing_fields.iter().collect::<Vec<_>>(), ["heroEquipment"], "Unexpected diagnostics: {diagnostics}");
+    assert_eq!(diagnostics.unknown_fields.iter().collect::<Vec<_>>(), ["newField", "troops[].newTroopField"], "Unexpected diagnostics: {diagnostics}");
+
+    let player = rest_manager.player("#8VURQ0YUJ").await.expect("Player should be decoded leniently");
+    assert_eq!(player, response.value);
+}
+
+#[tokio::test]
+async fn lenient_decode_missing_scalar_test() {
+    let mut player = get_changed_player();
+    player.as_object_mut().expect("Player should be an object").remove("expLevel");
+    let err = get_rest_manager(DecodeMode::Lenient, player).player("#8VURQ0YUJ").await.expect_err("Missing scalar should fail");
+    assert!(matches!(err.inner(), Error::Json(err) if err.to_string().contains("expLevel")), "Unexpected error: {err:#?}");
+}
+
+#[tokio::test]
+async fn standard_decode_test() {
+    let response = get_rest_manager(DecodeMode::Standard, get_changed_player()).get_raw::<APIPlayer>(PLAYER_PATH, None).await;
+    let err = response.expect_err("Missing field should fail");
+    assert!(matches!(err.inner(), Error::Json(_)), "Unexpected error: {err:#?}");
+
+    let mut player = get_changed_player();
+    player["heroEquipment"] = serde_json::json!([]);
+    let response = get_rest_manager(DecodeMode::Standard, player).get_raw::<APIPlayer>(PLAYER_PATH, None).await;
+    let response = response.expect("Unknown fields should be ignored");
+    assert!(response.diagnostics.is_empty(), "Standard decoding should not collect diagnostics");
+}
+
+#[tokio::test]
+async fn strict_decode_test() {
+    let mut player = get_changed_player();
+    player["heroEquipment"] = serde_json::json!([]);
+    let err = get_rest_manager(DecodeMode::Strict, player).player("#8VURQ0YUJ").await.expect_err("Unknown field should fail");
+    match err.inner() {
+        Error::Decode(diagnostics) => assert!(diagnostics.unknown_fields.contains("newField")),
+        other => panic!("Expected decode error, got {other:#?}"),
+    }
+    assert!(err.context().and_then(|context| context.body.as_ref()).is_some(), "Error should contain the response");
+}
+
+#[tokio::test]
+async fn lenient_decode_matches_standard_test() {
+    let transport = Arc::new(InMemoryTransport::new());
+    let clan = get_mock_json("clans/clan_info/2Q0Q0JG82.json");
+    transport.push_response(Method::GET, "clans/%232Q0Q0JG82", HttpResponse::new(200, clan.to_string()));
+    let war_log = get_mock_json("clans/war_log/2Q0Q0JG82.json");
+    transport.push_response(Method::GET, "clans/%232Q0Q0JG82/warlog", HttpResponse::new(200, war_log.to_string()));
+    let config = RestManagerConfig {
+        decode_mode: DecodeMode::Lenient,
+        ..get_in_memory_config(transport)
+    };
+    let rest_manager = RestManager::with_config("test_token", config).expect("Test object should be created");
+
+    let response = rest_manager.get_raw::<APIClan>("clans/%232Q0Q0JG82", None).await.expect("Clan should be decoded");
+    assert_eq!(response.value, serde_json::from_value::<APIClan>(clan).expect("Clan should be valid"));
+    assert!(response.diagnostics.missing_fields.is_empty(), "Unexpected diagnostics: {}", response.diagnostics);
+
+    let response = rest_manager.get_raw::<APIPagedResponse<APIClanWarLogEntry>>("clans/%232Q0Q0JG82/warlog", None).await;
+    let response = response.expect("War log should be decoded");
+    assert_eq!(response.value, serde_json::from_value::<APIPagedResponse<APIClanWarLogEntry>>(war_log).expect("War log should be valid"));
+}
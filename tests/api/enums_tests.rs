@@ -0,0 +1,83 @@
+use crate::api::utils::get_mock_json;
+use clash_forge::api::clans::models::{
+    APIBattleModifier, APIClan, APIClanCapitalRaidSeason, APIClanMemberRole, APIClanType, APIClanWar, APIClanWarLeagueGroup,
+    APIClanWarLeagueState, APIClanWarLogEntry, APIClanCapitalRaidSeasonState, APIWarFrequency, APIWarResult, APIWarState,
+};
+use clash_forge::api::common::models::APIPagedResponse;
+use clash_forge::api::players::models::{APIHouseElement, APIPlayer, APIVillageType, APIWarPreference};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Loads the mock data, replaces the values at the JSON pointers and checks that the model keeps them.
+fn check_unknown_values<T>(path: &str, pointers: &[&str]) -> T
+where
+    T: DeserializeOwned + Serialize,
+{
+    let mut json = get_mock_json(path);
+    for pointer in pointers {
+        *json.pointer_mut(pointer).unwrap_or_else(|| panic!("Mock data should contain {pointer}")) = "somethingNew".into();
+    }
+    let model: T = serde_json::from_value(json).expect("Model with unknown values should deserialize");
+    let json = serde_json::to_value(&model).expect("Model should serialize");
+    for pointer in pointers {
+        assert_eq!(json.pointer(pointer), Some(&"somethingNew".into()), "Unknown value at {pointer} should be kept");
+    }
+    model
+}
+
+#[test]
+fn clan_unknown_values_test() {
+    let clan: APIClan = check_unknown_values("clans/clan_info/2Q0Q0JG82.json", &["/type", "/warFrequency", "/memberList/0/role"]);
+    assert_eq!(clan.r#type, APIClanType::Unknown("somethingNew".to_string()));
+    assert!(clan.war_frequency.is_unknown());
+    assert!(clan.member_list.expect("Clan should have members")[0].role.is_unknown());
+}
+
+#[test]
+fn player_unknown_values_test() {
+    let player: APIPlayer = check_unknown_values(
+        "players/8VURQOYUJ.json",
+        &["/role", "/warPreference", "/playerHouse/elements/0/type", "/troops/0/village"],
+    );
+    assert_eq!(player.role, Some(APIClanMemberRole::Unknown("somethingNew".to_string())));
+    assert_eq!(player.war_preference, Some(APIWarPreference::Unknown("somethingNew".to_string())));
+    assert_eq!(player.player_house.expect("Player should have a house").elements[0].r#type, APIHouseElement::Unknown("somethingNew".to_string()));
+    assert_eq!(player.troops[0].village, APIVillageType::Unknown("somethingNew".to_string()));
+}
+
+#[test]
+fn war_unknown_values_test() {
+    let war: APIClanWar = check_unknown_values("clans/current_war/QY9RQ2G2_inWar.json", &["/state", "/battleModifier"]);
+    assert_eq!(war.state, APIWarState::Unknown("somethingNew".to_string()));
+    assert_eq!(war.battle_modifier, Some(APIBattleModifier::Unknown("somethingNew".to_string())));
+}
+
+#[test]
+fn war_log_unknown_values_test() {
+    let war_log: APIPagedResponse<APIClanWarLogEntry> = check_unknown_values("clans/war_log/2Q0Q0JG82.json", &["/items/0/result", "/items/0/battleModifier"]);
+    assert_eq!(war_log.items[0].result, Some(APIWarResult::Unknown("somethingNew".to_string())));
+}
+
+#[test]
+fn clan_war_league_group_unknown_values_test() {
+    let group: APIClanWarLeagueGroup = check_unknown_values("clans/clan_war_league_group/2Q0Q0JG82_inWar.json", &["/state"]);
+    assert_eq!(group.state, APIClanWarLeagueState::Unknown("somethingNew".to_string()));
+}
+
+#[test]
+fn raid_season_unknown_values_test() {
+    let seasons: APIPagedResponse<APIClanCapitalRaidSeason> = check_unknown_values("clans/clan_capital_raid_seasons/2Q0Q0JG82_ended.json", &["/items/0/state"]);
+    assert_eq!(seasons.items[0].state, APIClanCapitalRaidSeasonState::Unknown("somethingNew".to_string()));
+}
+
+#[test]
+fn known_values_test() {
+    assert_eq!(APIWarFrequency::from("always"), APIWarFrequency::Always);
+    assert_eq!(APIWarFrequency::from("unknown"), APIWarFrequency::NotSet);
+    assert!(!APIWarFrequency::NotSet.is_unknown());
+    assert_eq!(APIWarFrequency::from("sometimes"), APIWarFrequency::Unknown("sometimes".to_string()));
+    assert_eq!(APIVillageType::BuilderBase.as_str(), "builderBase");
+    assert_eq!(APIClanMemberRole::CoLeader.to_string(), "coLeader");
+    assert_eq!(serde_json::to_string(&APIWarState::InWar).expect("Value should serialize"), r#""inWar""#);
+    assert_eq!(serde_json::from_str::<APIWarState>(r#""warEnded""#).expect("Value should deserialize"), APIWarState::WarEnded);
+}
@@ -0,0 +1,101 @@
+use crate::api::utils::get_in_memory_rest_manager;
+use clash_forge::api::errors::{ApiErrorReason, Error as ApiError};
+use clash_forge::api::transport::{HttpResponse, InMemoryTransport};
+use clash_forge::errors::{Error, ErrorContext};
+use reqwest::Method;
+use std::sync::Arc;
+
+#[test]
+fn reason_parsed_from_body_test() {
+    let response = HttpResponse::new(403, r#"{"reason":"accessDenied","message":"Invalid authorization"}"#);
+    let err = ApiError::from_response(&response);
+    assert_eq!(err.reason, ApiErrorReason::AccessDenied);
+    assert_eq!(err.message, "Invalid authorization");
+    assert!(err.is_access_denied());
+    assert!(!err.is_retryable());
+}
+
+#[test]
+fn reason_falls_back_to_status_test() {
+    let err = ApiError::from_response(&HttpResponse::new(503, ""));
+    assert_eq!(err.reason, ApiErrorReason::InMaintenance);
+    assert!(err.is_maintenance());
+    assert!(err.is_retryable());
+
+    let err = ApiError::from_response(&HttpResponse::new(418, ""));
+    assert_eq!(err.reason, ApiErrorReason::Other("418 I'm a teapot".to_string()));
+}
+
+#[test]
+fn unknown_reason_is_kept_test() {
+    let response = HttpResponse::new(400, r#"{"reason":"somethingNew","message":"New reason"}"#);
+    let err = ApiError::from_response(&response);
+    assert_eq!(err.reason, ApiErrorReason::Other("somethingNew".to_string()));
+    assert_eq!(err.reason.to_string(), "somethingNew");
+}
+
+#[test]
+fn reason_serde_round_trip_test() {
+    let reasons = [ApiErrorReason::RequestThrottled, ApiErrorReason::Other("somethingNew".to_string())];
+    for reason in reasons {
+        let json = serde_json::to_string(&reason).expect("Reason should serialize");
+        let parsed: ApiErrorReason = serde_json::from_str(&json).expect("Reason should deserialize");
+        assert_eq!(parsed, reason);
+    }
+    assert_eq!(serde_json::to_string(&ApiErrorReason::NotFound).unwrap(), r#""notFound""#);
+}
+
+#[test]
+fn crate_error_predicates_test() {
+    let err = Error::Api(ApiError::new(404, ApiErrorReason::NotFound, "Not found".to_string()));
+    assert!(err.is_not_found());
+    assert!(!err.is_retryable());
+    assert_eq!(err.api_reason(), Some(&ApiErrorReason::NotFound));
+
+    let err = Error::Api(ApiError::new(429, ApiErrorReason::RequestThrottled, "Throttled".to_string()));
+    assert!(err.is_throttled());
+    assert!(err.is_retryable());
+
+    assert!(!Error::InvalidToken.is_retryable());
+    assert_eq!(Error::InvalidToken.api_reason(), None);
+}
+
+#[tokio::test]
+async fn api_error_context_test() {
+    let transport = Arc::new(InMemoryTransport::new());
+    let response = HttpResponse::new(429, r#"{"reason":"requestThrottled","message":"Slow down"}"#).with_header("retry-after", "3");
+    transport.push_response(Method::GET, "players/%232PP", response);
+
+    let err = get_in_memory_rest_manager(transport).player("2pp").await.expect_err("Request should fail");
+    assert!(err.is_throttled());
+    let context = err.context().expect("Error should have a context");
+    assert_eq!(context.method, "GET");
+    assert_eq!(context.endpoint, "players/%232PP");
+    assert_eq!(context.tag.as_deref(), Some("#2PP"));
+    assert_eq!(context.status, Some(429));
+    assert_eq!(context.header("retry-after"), Some("3"));
+    assert!(context.body.as_deref().is_some_and(|body| body.contains("Slow down")));
+    assert!(err.to_string().contains("GET players/%232PP"), "Unexpected message: {err}");
+}
+
+#[tokio::test]
+async fn json_error_context_test() {
+    let transport = Arc::new(InMemoryTransport::new());
+    transport.push_response(Method::GET, "players/%232PP", HttpResponse::new(200, r#"{"tag":42}"#));
+
+    let err = get_in_memory_rest_manager(transport).player("#2PP").await.expect_err("Request should fail");
+    assert!(matches!(err.inner(), Error::Json(_)), "Expected JSON error, got {err:#?}");
+    let context = err.context().expect("Error should have a context");
+    assert_eq!(context.status, Some(200));
+    assert_eq!(context.body.as_deref(), Some(r#"{"tag":42}"#));
+}
+
+#[test]
+fn context_body_is_truncated_test() {
+    let body = "x".repeat(10_000);
+    let context = ErrorContext::new("GET", "clans", Default::default()).with_response(500, Default::default(), body.as_bytes());
+    let truncated = context.body.expect("Body should be set");
+    assert!(truncated.len() < body.len());
+    assert!(truncated.ends_with("(10000 bytes total)"));
+    assert_eq!(context.tag, None);
+}
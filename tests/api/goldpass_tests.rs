@@ -2,7 +2,7 @@ use crate::api::utils::get_test_rest_manager;
 
 macro_rules! format_path {
     ($name:expr) => {
-        crate::api::utils::get_mock_data_path((format!("goldpass/{}.json", $name)))
+        crate::api::utils::get_mock_data_path(format!("goldpass/{}.json", $name))
     };
 }
 
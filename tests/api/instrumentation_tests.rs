@@ -0,0 +1,42 @@
+use clash_forge::api::instrumentation::endpoint_template;
+
+#[test]
+fn endpoint_template_test() {
+    assert_eq!(endpoint_template("clans/%232Q0Q0JG82/members"), "clans/{tag}/members");
+    assert_eq!(endpoint_template("clanwarleagues/wars/%238QJ2UQ8"), "clanwarleagues/wars/{tag}");
+    assert_eq!(endpoint_template("leagues/29000022/seasons/2024-12"), "leagues/{id}/seasons/{season_id}");
+    assert_eq!(endpoint_template("locations/32000225/rankings/players-builder-base"), "locations/{id}/rankings/players-builder-base");
+    assert_eq!(endpoint_template("goldpass/seasons/current"), "goldpass/seasons/current");
+}
+
+#[cfg(feature = "metrics")]
+#[test]
+fn metrics_test() {
+    use clash_forge::api::transport::{HttpResponse, InMemoryTransport};
+    use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+    use std::sync::Arc;
+
+    let transport = Arc::new(InMemoryTransport::new());
+    let body = std::fs::read(crate::api::utils::get_mock_data_path("clans/clan_info/2Q0Q0JG82.json")).expect("Mock data should exist");
+    transport.push_response(reqwest::Method::GET, "clans/%232Q0Q0JG82", HttpResponse::new(200, body));
+    let rest_manager = crate::api::utils::get_in_memory_rest_manager(transport);
+
+    let recorder = DebuggingRecorder::new();
+    let snapshotter = recorder.snapshotter();
+    metrics::with_local_recorder(&recorder, || {
+        let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().expect("Runtime should be created");
+        let result = runtime.block_on(rest_manager.clan_info("2Q0Q0JG82"));
+        assert!(result.is_ok(), "Clan info request returned an error: {:#?}", result.err());
+    });
+
+    let requests = snapshotter
+        .snapshot()
+        .into_vec()
+        .into_iter()
+        .find(|(key, ..)| key.key().name() == "clash_forge_requests_total")
+        .expect("Request counter should be recorded");
+    let labels: Vec<_> = requests.0.key().labels().map(|label| (label.key().to_string(), label.value().to_string())).collect();
+    assert!(labels.contains(&("endpoint".to_string(), "clans/{tag}".to_string())), "Unexpected labels: {:?}", labels);
+    assert!(labels.contains(&("status".to_string(), "200".to_string())), "Unexpected labels: {:?}", labels);
+    assert_eq!(requests.3, DebugValue::Counter(1));
+}
@@ -0,0 +1,166 @@
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use clash_forge::api::key_manager::manager::{KeyManager, KeyManagerConfig};
+use clash_forge::api::key_manager::models::APILoginRequest;
+use clash_forge::api::rest_manager::RestManagerConfig;
+use mockito::Matcher;
+
+const IP: &str = "203.0.113.7";
+
+fn temporary_token() -> String {
+    let claims = format!(r#"{{"limits":[{{"type":"throttling"}},{{"type":"client","cidrs":["{IP}/32"]}}]}}"#);
+    format!("header.{}.signature", URL_SAFE_NO_PAD.encode(claims))
+}
+
+fn get_test_config(base_url: &str) -> KeyManagerConfig {
+    KeyManagerConfig {
+        base_url: base_url.to_string(),
+        key_count: 2,
+        ..Default::default()
+    }
+}
+
+fn key(id: &str, name: &str, ip: &str) -> String {
+    format!(r#"{{"id":"{id}","name":"{name}","description":null,"cidrRanges":["{ip}"],"scopes":["clash"],"key":"token_{id}"}}"#)
+}
+
+async fn mock_login(server: &mut mockito::Server) -> mockito::Mock {
+    server
+        .mock("POST", "/login")
+        .match_body(Matcher::Json(serde_json::json!({"email": "dev@example.com", "password": "secret"})))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_header("set-cookie", "session=abc; Path=/")
+        .with_body(format!(r#"{{"temporaryAPIToken":"{}","sessionExpiresInSeconds":3600}}"#, temporary_token()))
+        .create_async()
+        .await
+}
+
+#[tokio::test]
+async fn key_manager_ensure_keys_test() {
+    let mut server = mockito::Server::new_async().await;
+    let url = server.url();
+    let _login = mock_login(&mut server).await;
+    let _list = server
+        .mock("POST", "/apikey/list")
+        .match_header("cookie", "session=abc")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(format!(
+            r#"{{"keys":[{},{},{}]}}"#,
+            key("stale", "clash-forge", "198.51.100.1"),
+            key("valid", "clash-forge", IP),
+            key("foreign", "other-bot", "198.51.100.1"),
+        ))
+        .create_async()
+        .await;
+    let revoke = server
+        .mock("POST", "/apikey/revoke")
+        .match_header("cookie", "session=abc")
+        .match_body(Matcher::Json(serde_json::json!({"id": "stale"})))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"status":{"code":0,"message":"ok"}}"#)
+        .expect(1)
+        .create_async()
+        .await;
+    let create = server
+        .mock("POST", "/apikey/create")
+        .match_header("cookie", "session=abc")
+        .match_body(Matcher::PartialJson(serde_json::json!({"name": "clash-forge", "cidrRanges": [IP], "scopes": ["clash"]})))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(format!(r#"{{"key":{}}}"#, key("created", "clash-forge", IP)))
+        .expect(1)
+        .create_async()
+        .await;
+
+    let key_manager = KeyManager::login("dev@example.com", "secret", get_test_config(&url)).await;
+    assert!(key_manager.is_ok(), "Login returned an error: {:#?}", key_manager.err());
+    let key_manager = key_manager.unwrap();
+    assert_eq!(key_manager.ip(), IP);
+
+    let tokens = key_manager.ensure_keys().await;
+    assert!(tokens.is_ok(), "Ensure keys returned an error: {:#?}", tokens.err());
+    assert_eq!(tokens.unwrap(), vec!["token_valid".to_string(), "token_created".to_string()]);
+    revoke.assert_async().await;
+    create.assert_async().await;
+}
+
+#[tokio::test]
+async fn key_manager_rest_manager_test() {
+    let mut server = mockito::Server::new_async().await;
+    let url = server.url();
+    let _login = mock_login(&mut server).await;
+    let _list = server
+        .mock("POST", "/apikey/list")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(format!(r#"{{"keys":[{},{}]}}"#, key("first", "clash-forge", IP), key("second", "clash-forge", IP)))
+        .create_async()
+        .await;
+    let api = server
+        .mock("GET", "/goldpass/seasons/current")
+        .match_header("authorization", "Bearer token_first")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"startTime":"20250101T080000.000Z","endTime":"20250201T080000.000Z"}"#)
+        .create_async()
+        .await;
+
+    let key_manager = KeyManager::login("dev@example.com", "secret", get_test_config(&url)).await.expect("Login should succeed");
+    let config = RestManagerConfig {
+        base_url: url.clone(),
+        ..Default::default()
+    };
+    let rest_manager = key_manager.rest_manager(config).await.expect("Rest manager should be created");
+    assert_eq!(rest_manager.key_health().len(), 2);
+    let result = rest_manager.goldpass().await;
+    assert!(result.is_ok(), "Goldpass request returned an error: {:#?}", result.err());
+    api.assert_async().await;
+}
+
+#[tokio::test]
+async fn key_manager_key_limit_test() {
+    let mut server = mockito::Server::new_async().await;
+    let url = server.url();
+    let _login = mock_login(&mut server).await;
+    let foreign_keys: Vec<_> = (0..10).map(|index| key(&index.to_string(), "other-bot", IP)).collect();
+    let _list = server
+        .mock("POST", "/apikey/list")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(format!(r#"{{"keys":[{}]}}"#, foreign_keys.join(",")))
+        .create_async()
+        .await;
+
+    let key_manager = KeyManager::login("dev@example.com", "secret", get_test_config(&url)).await.expect("Login should succeed");
+    let result = key_manager.ensure_keys().await;
+    assert!(result.is_err(), "Keys should not be created above the account limit: {:#?}", result);
+}
+
+#[tokio::test]
+async fn key_manager_invalid_credentials_test() {
+    let mut server = mockito::Server::new_async().await;
+    let url = server.url();
+    let _login = server
+        .mock("POST", "/login")
+        .with_status(403)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"status":{"code":403,"message":"invalid credentials"}}"#)
+        .create_async()
+        .await;
+    let result = KeyManager::login("dev@example.com", "wrong", get_test_config(&url)).await;
+    assert!(result.is_err(), "Login with invalid credentials should fail");
+}
+
+#[test]
+fn login_request_debug_test() {
+    let request = APILoginRequest {
+        email: "dev@example.com".to_string(),
+        password: "secret".to_string(),
+    };
+    let debug = format!("{request:?}");
+    assert!(debug.contains("dev@example.com"));
+    assert!(!debug.contains("secret"), "Password should be redacted: {debug}");
+}
@@ -0,0 +1,85 @@
+use crate::api::utils::get_test_config;
+use clash_forge::api::key_pool::KeySelection;
+use clash_forge::api::rest_manager::{RestManager, RestManagerConfig};
+
+const GOLDPASS_BODY: &str = r#"{"startTime":"20250101T080000.000Z","endTime":"20250201T080000.000Z"}"#;
+
+fn get_pool_rest_manager(base_url: &str, key_selection: KeySelection) -> RestManager {
+    let config = RestManagerConfig {
+        key_selection,
+        ..get_test_config(base_url)
+    };
+    RestManager::with_tokens(["token_a", "token_b"], config).expect("Test object should be created")
+}
+
+#[tokio::test]
+async fn key_pool_round_robin_test() {
+    let mut server = mockito::Server::new_async().await;
+    let url = server.url();
+    let mut mocks = Vec::new();
+    for token in ["token_a", "token_b"] {
+        let mock = server
+            .mock("GET", "/goldpass/seasons/current")
+            .match_header("authorization", format!("Bearer {token}").as_str())
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(GOLDPASS_BODY)
+            .expect(2)
+            .create_async()
+            .await;
+        mocks.push(mock);
+    }
+    let rest_manager = get_pool_rest_manager(&url, KeySelection::RoundRobin);
+    for _ in 0..4 {
+        let result = rest_manager.goldpass().await;
+        assert!(result.is_ok(), "Goldpass request returned an error: {:#?}", result.err());
+    }
+    for mock in mocks {
+        mock.assert_async().await;
+    }
+    let health = rest_manager.key_health();
+    assert!(health.iter().all(|key| key.requests == 2 && key.is_available()), "Unexpected key health: {:#?}", health);
+}
+
+#[tokio::test]
+async fn key_pool_failover_test() {
+    let mut server = mockito::Server::new_async().await;
+    let url = server.url();
+    let denied = server
+        .mock("GET", "/goldpass/seasons/current")
+        .match_header("authorization", "Bearer token_a")
+        .with_status(403)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"reason":"accessDenied","message":"Invalid authorization"}"#)
+        .expect(1)
+        .create_async()
+        .await;
+    let allowed = server
+        .mock("GET", "/goldpass/seasons/current")
+        .match_header("authorization", "Bearer token_b")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(GOLDPASS_BODY)
+        .expect(3)
+        .create_async()
+        .await;
+    let rest_manager = get_pool_rest_manager(&url, KeySelection::LeastRecentlyUsed);
+    for _ in 0..3 {
+        let result = rest_manager.goldpass().await;
+        assert!(result.is_ok(), "Goldpass request should fail over to another key: {:#?}", result.err());
+    }
+    denied.assert_async().await;
+    allowed.assert_async().await;
+
+    let health = rest_manager.key_health();
+    assert!(!health[0].is_available(), "Denied key should be benched: {:#?}", health[0]);
+    assert_eq!(health[0].failures, 1);
+    assert_eq!(health[0].token_hint, "...en_a");
+    assert!(health[1].is_available(), "Working key should stay available: {:#?}", health[1]);
+}
+
+#[test]
+fn key_pool_empty_test() {
+    let result = RestManager::with_tokens(Vec::<String>::new(), RestManagerConfig::default());
+    assert!(result.is_err(), "Key pool without tokens should not be created");
+}
@@ -3,7 +3,7 @@ use crate::api::utils::get_test_rest_manager;
 
 macro_rules! format_path {
     ($name:expr) => {
-        crate::api::utils::get_mock_data_path((format!("labels/{}.json", $name)))
+        crate::api::utils::get_mock_data_path(format!("labels/{}.json", $name))
     };
 }
 
@@ -0,0 +1,79 @@
+use crate::api::utils::get_in_memory_config;
+use clash_forge::api::middleware::Middleware;
+use clash_forge::api::rest_manager::{RestManager, RestManagerConfig};
+use clash_forge::api::transport::{HttpRequest, HttpResponse, InMemoryTransport};
+use reqwest::header::HeaderValue;
+use reqwest::Method;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+const GOLDPASS_BODY: &str = r#"{"startTime":"20250101T080000.000Z","endTime":"20250201T080000.000Z"}"#;
+
+#[derive(Debug, Default)]
+struct Recorder {
+    name: &'static str,
+    calls: Arc<Mutex<Vec<String>>>,
+}
+
+impl Middleware for Recorder {
+    fn before_request(&self, request: &mut HttpRequest) -> Option<HttpResponse> {
+        request.headers.insert("x-correlation-id", HeaderValue::from_static("42"));
+        self.calls.lock().unwrap().push(format!("before {}", self.name));
+        None
+    }
+
+    fn after_response(&self, request: &HttpRequest, response: &mut HttpResponse, _elapsed: Duration) {
+        let authorization = request.redacted_headers().get("authorization").cloned();
+        assert_eq!(authorization, Some(HeaderValue::from_static("Bearer <redacted>")));
+        self.calls.lock().unwrap().push(format!("after {} {}", self.name, response.status.as_u16()));
+    }
+}
+
+#[derive(Debug)]
+struct Fixture;
+
+impl Middleware for Fixture {
+    fn before_request(&self, request: &mut HttpRequest) -> Option<HttpResponse> {
+        request.url.ends_with("goldpass/seasons/current").then(|| HttpResponse::new(200, GOLDPASS_BODY))
+    }
+}
+
+fn get_middleware_rest_manager(transport: Arc<InMemoryTransport>, middleware: Vec<Arc<dyn Middleware>>) -> RestManager {
+    let config = RestManagerConfig {
+        middleware,
+        ..get_in_memory_config(transport)
+    };
+    RestManager::with_config("test_token", config).expect("Test object should be created")
+}
+
+#[tokio::test]
+async fn middleware_order_test() {
+    let transport = Arc::new(InMemoryTransport::new());
+    transport.push_response(Method::GET, "goldpass/seasons/current", HttpResponse::new(200, GOLDPASS_BODY));
+    let calls = Arc::new(Mutex::new(Vec::new()));
+    let middleware: Vec<Arc<dyn Middleware>> = vec![
+        Arc::new(Recorder { name: "first", calls: calls.clone() }),
+        Arc::new(Recorder { name: "second", calls: calls.clone() }),
+    ];
+
+    let result = get_middleware_rest_manager(transport.clone(), middleware).goldpass().await;
+    assert!(result.is_ok(), "Goldpass request returned an error: {:#?}", result.err());
+    assert_eq!(*calls.lock().unwrap(), vec!["before first", "before second", "after second 200", "after first 200"]);
+    assert_eq!(transport.requests()[0].headers.get("x-correlation-id"), Some(&HeaderValue::from_static("42")));
+}
+
+#[tokio::test]
+async fn middleware_short_circuit_test() {
+    let transport = Arc::new(InMemoryTransport::new());
+    let calls = Arc::new(Mutex::new(Vec::new()));
+    let middleware: Vec<Arc<dyn Middleware>> = vec![
+        Arc::new(Recorder { name: "outer", calls: calls.clone() }),
+        Arc::new(Fixture),
+        Arc::new(Recorder { name: "inner", calls: calls.clone() }),
+    ];
+
+    let result = get_middleware_rest_manager(transport.clone(), middleware).goldpass().await;
+    assert!(result.is_ok(), "Goldpass request should be served by the fixture: {:#?}", result.err());
+    assert!(transport.requests().is_empty(), "Transport should not be called");
+    assert_eq!(*calls.lock().unwrap(), vec!["before outer", "after outer 200"]);
+}
@@ -7,4 +7,22 @@ mod leagues_tests;
 mod labels_tests;
 mod clans_tests;
 mod clans_search_tests;
-mod pagination_tests;
\ No newline at end of file
+mod pagination_tests;
+#[cfg(feature = "blocking")]
+mod blocking_tests;
+mod cache_tests;
+mod decode_tests;
+mod enums_tests;
+mod errors_tests;
+mod instrumentation_tests;
+mod key_manager_tests;
+mod key_pool_tests;
+mod middleware_tests;
+mod paginator_tests;
+mod rate_limiter_tests;
+mod response_tests;
+mod retry_tests;
+mod tag_id_tests;
+mod tags_tests;
+mod timestamp_tests;
+mod transport_tests;
\ No newline at end of file
@@ -4,8 +4,8 @@ use clash_forge::api::common::pagination::PaginationOptions;
 fn pagination_correct_query_parameters_test() {
     let pagination_options = PaginationOptions::builder()
         .limit(20)
-        .after("abc123".to_string())
-        .before("xyz789".to_string())
+        .after("abc123")
+        .before("xyz789")
         .build();
     let query_parameters = pagination_options.to_query_parameters();
     assert_eq!(query_parameters.get("limit"), Some(&"20".to_string()));
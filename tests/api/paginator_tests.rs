@@ -0,0 +1,138 @@
+use crate::api::utils::{get_in_memory_rest_manager, labels_page, labels_page_with_cursors};
+use clash_forge::api::common::pagination::{PaginationCheckpoint, PaginationDirection};
+use clash_forge::api::transport::{HttpResponse, InMemoryTransport};
+use futures::StreamExt;
+use reqwest::Method;
+use std::sync::Arc;
+
+#[tokio::test]
+async fn paginator_follows_cursors_test() {
+    let transport = Arc::new(InMemoryTransport::new());
+    transport.push_response(Method::GET, "labels/players", labels_page(&[1, 2], Some("cursor1")));
+    transport.push_response(Method::GET, "labels/players", labels_page(&[3], None));
+
+    let rest_manager = get_in_memory_rest_manager(transport.clone());
+    let labels = rest_manager.player_labels_paginated().page_size(2).collect_all().await.expect("Labels should be retrieved");
+    assert_eq!(labels.iter().map(|label| label.id).collect::<Vec<_>>(), vec![1, 2, 3]);
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].query.get("limit").map(String::as_str), Some("2"));
+    assert_eq!(requests[0].query.get("after"), None);
+    assert_eq!(requests[1].query.get("after").map(String::as_str), Some("cursor1"));
+}
+
+#[tokio::test]
+async fn paginator_max_items_test() {
+    let transport = Arc::new(InMemoryTransport::new());
+    transport.push_response(Method::GET, "labels/clans", labels_page(&[1, 2], Some("cursor1")));
+    transport.push_response(Method::GET, "labels/clans", labels_page(&[3, 4], Some("cursor2")));
+
+    let rest_manager = get_in_memory_rest_manager(transport.clone());
+    let labels = rest_manager.clan_labels_paginated().max_items(3).collect_all().await.expect("Labels should be retrieved");
+    assert_eq!(labels.len(), 3);
+    assert_eq!(transport.requests().len(), 2, "Pages after the limit should not be requested");
+}
+
+#[tokio::test]
+async fn paginator_is_lazy_and_stops_on_error_test() {
+    let transport = Arc::new(InMemoryTransport::new());
+    transport.push_response(Method::GET, "clans/%232PP/members", HttpResponse::new(503, r#"{"reason":"inMaintenance","message":"Maintenance"}"#));
+
+    let rest_manager = get_in_memory_rest_manager(transport.clone());
+    let paginator = rest_manager.clan_members_paginated("#2PP").expect("Tag should be valid");
+    assert!(transport.requests().is_empty(), "No request should be sent before the stream is polled");
+
+    let mut stream = Box::pin(paginator.into_stream());
+    let first = stream.next().await.expect("Stream should yield the error");
+    assert!(first.is_err_and(|err| err.is_maintenance()));
+    assert!(stream.next().await.is_none(), "Stream should end after an error");
+}
+
+#[tokio::test]
+async fn paginator_backward_test() {
+    let transport = Arc::new(InMemoryTransport::new());
+    transport.push_response(Method::GET, "labels/players", labels_page_with_cursors(&[3, 4], Some("cursor4"), Some("cursor2")));
+    transport.push_response(Method::GET, "labels/players", labels_page_with_cursors(&[1, 2], Some("cursor2"), None));
+
+    let rest_manager = get_in_memory_rest_manager(transport.clone());
+    let labels = rest_manager
+        .player_labels_paginated()
+        .direction(PaginationDirection::Backward)
+        .start_at("cursor5")
+        .collect_all()
+        .await
+        .expect("Labels should be retrieved");
+    assert_eq!(labels.iter().map(|label| label.id).collect::<Vec<_>>(), vec![4, 3, 2, 1]);
+
+    let requests = transport.requests();
+    assert_eq!(requests[0].query.get("before").map(String::as_str), Some("cursor5"));
+    assert_eq!(requests[1].query.get("before").map(String::as_str), Some("cursor2"));
+    assert_eq!(requests[1].query.get("after"), None);
+}
+
+#[tokio::test]
+async fn paginator_checkpoint_resume_test() {
+    let pages = || {
+        let transport = Arc::new(InMemoryTransport::new());
+        transport.push_response(Method::GET, "labels/clans", labels_page(&[1, 2], Some("cursor1")));
+        transport.push_response(Method::GET, "labels/clans", labels_page(&[3, 4], Some("cursor2")));
+        transport.push_response(Method::GET, "labels/clans", labels_page(&[5], None));
+        transport
+    };
+
+    let mut paginator = get_in_memory_rest_manager(pages()).clan_labels_paginated();
+    for _ in 0..3 {
+        paginator.next().await.expect("Item should exist").expect("Item should be retrieved");
+    }
+    let checkpoint = paginator.checkpoint();
+    assert_eq!(checkpoint.endpoint, "labels/clans");
+    assert_eq!(checkpoint.cursor.as_deref(), Some("cursor1"));
+    assert_eq!(checkpoint.skip, 1);
+    assert_eq!(checkpoint.items_seen, 3);
+
+    let json = serde_json::to_string(&checkpoint).expect("Checkpoint should serialize");
+    let checkpoint: PaginationCheckpoint = serde_json::from_str(&json).expect("Checkpoint should deserialize");
+
+    let transport = Arc::new(InMemoryTransport::new());
+    transport.push_response(Method::GET, "labels/clans", labels_page(&[3, 4], Some("cursor2")));
+    transport.push_response(Method::GET, "labels/clans", labels_page(&[5], None));
+    let mut paginator = get_in_memory_rest_manager(transport.clone())
+        .clan_labels_paginated()
+        .resume(checkpoint)
+        .expect("Checkpoint should be accepted");
+    let mut labels = Vec::new();
+    while let Some(label) = paginator.next().await {
+        labels.push(label.expect("Item should be retrieved").id);
+    }
+    assert_eq!(labels, vec![4, 5]);
+    assert_eq!(transport.requests()[0].query.get("after").map(String::as_str), Some("cursor1"));
+
+    let checkpoint = paginator.checkpoint();
+    assert!(checkpoint.exhausted);
+    assert_eq!(checkpoint.items_seen, 5);
+}
+
+#[tokio::test]
+async fn paginator_checkpoint_of_other_endpoint_test() {
+    let rest_manager = get_in_memory_rest_manager(Arc::new(InMemoryTransport::new()));
+    let checkpoint = rest_manager.player_labels_paginated().checkpoint();
+    let result = rest_manager.clan_labels_paginated().resume(checkpoint);
+    assert!(result.is_err(), "Checkpoint of another endpoint should be rejected");
+}
+
+#[tokio::test]
+async fn paginator_clone_test() {
+    let transport = Arc::new(InMemoryTransport::new());
+    transport.push_response(Method::GET, "labels/players", labels_page(&[1, 2], None));
+
+    let rest_manager = get_in_memory_rest_manager(transport.clone());
+    let mut paginator = rest_manager.player_labels_paginated();
+    let first = paginator.next().await.expect("Label should exist").expect("Label should be retrieved");
+    let copy = paginator.clone();
+    let rest = paginator.collect_all().await.expect("Labels should be retrieved");
+    let copy_rest = copy.collect_all().await.expect("Labels should be retrieved");
+    assert_eq!(first.id, 1);
+    assert_eq!(rest, copy_rest, "A clone should continue from the same position");
+    assert_eq!(transport.requests().len(), 1);
+}
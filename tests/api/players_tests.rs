@@ -42,10 +42,10 @@ async fn player_verify_test() {
     let valid_token = "valid_token";
     let _m = server
         .mock("POST", format_url!("{}/verifytoken", normalized_tag))
-        .match_request(|req| {
+        .match_request(move |req| {
             let body = req.body().expect("Should have a body").as_slice();
             let request: APIVerifyTokenRequest = serde_json::from_slice(body).expect("Should be able to deserialize request body");
-            request.token == valid_token.to_string()
+            request.token == valid_token
         })
         .with_status(200)
         .with_header("content-type", "application/json")
@@ -55,10 +55,10 @@ async fn player_verify_test() {
 
     let _m_invalid = server
         .mock("POST", format_url!("{}/verifytoken", normalized_tag))
-        .match_request(|req| {
+        .match_request(move |req| {
             let body = req.body().expect("Should have a body").as_slice();
             let request: APIVerifyTokenRequest = serde_json::from_slice(body).expect("Should be able to deserialize request body");
-            request.token != valid_token.to_string()
+            request.token != valid_token
         })
         .with_status(200)
         .with_header("content-type", "application/json")
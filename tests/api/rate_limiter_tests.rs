@@ -0,0 +1,61 @@
+use crate::api::utils::get_test_config;
+use clash_forge::api::rate_limiter::{RateLimit, RateLimiter};
+use clash_forge::api::rest_manager::{RestManager, RestManagerConfig};
+use std::time::{Duration, Instant};
+
+#[tokio::test]
+async fn rate_limiter_burst_test() {
+    let limiter = RateLimiter::new(RateLimit::new(10, 3));
+    let started = Instant::now();
+    for _ in 0..3 {
+        limiter.acquire().await;
+    }
+    assert!(started.elapsed() < Duration::from_millis(50), "Burst requests should not wait: {:?}", started.elapsed());
+
+    limiter.acquire().await;
+    limiter.acquire().await;
+    assert!(started.elapsed() >= Duration::from_millis(190), "Requests above the burst should wait: {:?}", started.elapsed());
+}
+
+#[tokio::test]
+async fn rate_limiter_shared_between_clones_test() {
+    let mut server = mockito::Server::new_async().await;
+    let url = server.url();
+    let _m = server
+        .mock("GET", "/goldpass/seasons/current")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"startTime":"20250101T080000.000Z","endTime":"20250201T080000.000Z"}"#)
+        .expect(4)
+        .create_async()
+        .await;
+    let config = RestManagerConfig {
+        rate_limit: Some(RateLimit::new(20, 2)),
+        ..get_test_config(&url)
+    };
+    let rest_manager = RestManager::with_config("test_token", config).expect("Test object should be created");
+
+    let started = Instant::now();
+    let tasks: Vec<_> = (0..4)
+        .map(|_| {
+            let rest_manager = rest_manager.clone();
+            tokio::spawn(async move { rest_manager.goldpass().await })
+        })
+        .collect();
+    for task in tasks {
+        let result = task.await.expect("Task should not panic");
+        assert!(result.is_ok(), "Goldpass request returned an error: {:#?}", result.err());
+    }
+    assert!(started.elapsed() >= Duration::from_millis(90), "Clones should share one budget: {:?}", started.elapsed());
+}
+
+#[tokio::test]
+async fn rate_limiter_zero_rate_test() {
+    let limiter = RateLimiter::new(RateLimit { requests_per_second: 0, burst: 0 });
+    assert_eq!(limiter.limit(), RateLimit::new(1, 1));
+
+    let started = Instant::now();
+    limiter.acquire().await;
+    limiter.acquire().await;
+    assert!(started.elapsed() >= Duration::from_millis(900), "Requests above the burst should wait: {:?}", started.elapsed());
+}
@@ -0,0 +1,69 @@
+use crate::api::utils::{get_in_memory_config, get_mock_json};
+use clash_forge::api::cache::InMemoryCache;
+use clash_forge::api::decode::DecodeMode;
+use clash_forge::api::players::models::APIPlayer;
+use clash_forge::api::rest_manager::{RestManager, RestManagerConfig};
+use clash_forge::api::transport::{HttpResponse, InMemoryTransport};
+use reqwest::Method;
+use std::sync::Arc;
+use std::time::Duration;
+
+fn get_player_body() -> String {
+    let mut player = get_mock_json("players/8VURQOYUJ.json");
+    player["newField"] = serde_json::json!({ "level": 3 });
+    player.to_string()
+}
+
+fn get_rest_manager(transport: Arc<InMemoryTransport>) -> RestManager {
+    let config = RestManagerConfig {
+        cache: Some(Arc::new(InMemoryCache::new())),
+        decode_mode: DecodeMode::Lenient,
+        ..get_in_memory_config(transport)
+    };
+    RestManager::with_config("test_token", config).expect("Test object should be created")
+}
+
+#[tokio::test]
+async fn raw_response_test() {
+    let body = get_player_body();
+    let transport = Arc::new(InMemoryTransport::new());
+    let response = HttpResponse::new(200, body.clone())
+        .with_header("cache-control", "public max-age=60")
+        .with_header("date", "Sat, 18 Oct 2025 08:00:00 GMT");
+    transport.push_response(Method::GET, "players/%238VURQ0YUJ", response);
+    let rest_manager = get_rest_manager(transport);
+
+    let response = rest_manager.get_raw::<APIPlayer>("players/%238VURQ0YUJ", None).await.expect("Response should be decoded");
+    assert_eq!(response.value.tag, "#8VURQ0YUJ");
+    assert_eq!(response.status, Some(200));
+    assert!(!response.is_cached());
+    assert_eq!(response.bytes, body.as_bytes());
+    assert_eq!(response.header("date"), Some("Sat, 18 Oct 2025 08:00:00 GMT"));
+    assert_eq!(response.field("/newField/level"), Some(&serde_json::json!(3)));
+    assert!(response.diagnostics.unknown_fields.contains("newField"), "Unexpected diagnostics: {}", response.diagnostics);
+    let max_age = response.max_age().expect("Response should have an expiry");
+    assert!(max_age <= Duration::from_secs(60) && max_age > Duration::from_secs(50));
+
+    let cached = rest_manager.get_raw::<serde_json::Value>("players/%238VURQ0YUJ", None).await.expect("Response should be cached");
+    assert!(cached.is_cached());
+    assert!(cached.headers.is_empty());
+    assert_eq!(cached.expires_at, response.expires_at);
+    assert_eq!(cached.value, response.json);
+    assert_eq!(cached.bytes, response.json.to_string().into_bytes());
+    assert!(cached.diagnostics.is_empty());
+}
+
+#[tokio::test]
+async fn raw_response_without_expiry_test() {
+    let transport = Arc::new(InMemoryTransport::new());
+    let response = HttpResponse::new(200, get_player_body()).with_header("cache-control", "no-store");
+    transport.push_response(Method::GET, "players/%238VURQ0YUJ", response);
+    let rest_manager = get_rest_manager(transport.clone());
+
+    let response = rest_manager.get_raw::<APIPlayer>("players/%238VURQ0YUJ", None).await.expect("Response should be decoded");
+    assert_eq!(response.expires_at, None);
+    assert_eq!(response.max_age(), None);
+
+    rest_manager.get_raw::<APIPlayer>("players/%238VURQ0YUJ", None).await.expect("Response should be decoded");
+    assert_eq!(transport.requests().len(), 2, "Responses without expiry should not be cached");
+}
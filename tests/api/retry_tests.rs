@@ -0,0 +1,121 @@
+use crate::api::utils::get_test_config;
+use clash_forge::api::errors::ApiErrorReason;
+use clash_forge::api::rest_manager::{RestManager, RestManagerConfig};
+use clash_forge::api::retry::RetryPolicy;
+use std::time::Duration;
+
+fn get_retry_rest_manager(base_url: &str, retry_policy: RetryPolicy) -> RestManager {
+    let config = RestManagerConfig {
+        retry_policy,
+        ..get_test_config(base_url)
+    };
+    RestManager::with_config("test_token", config).expect("Test object should be created")
+}
+
+fn fast_retry_policy(max_attempts: u32) -> RetryPolicy {
+    RetryPolicy::builder()
+        .max_attempts(max_attempts)
+        .initial_backoff(Duration::from_millis(1))
+        .max_backoff(Duration::from_millis(10))
+        .jitter(false)
+        .build()
+}
+
+#[tokio::test]
+async fn retry_throttled_then_success_test() {
+    let mut server = mockito::Server::new_async().await;
+    let url = server.url();
+    let throttled = server
+        .mock("GET", "/goldpass/seasons/current")
+        .with_status(429)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"reason":"requestThrottled","message":"Slow down"}"#)
+        .expect(2)
+        .create_async()
+        .await;
+    let success = server
+        .mock("GET", "/goldpass/seasons/current")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"startTime":"20250101T080000.000Z","endTime":"20250201T080000.000Z"}"#)
+        .expect(1)
+        .create_async()
+        .await;
+    let result = get_retry_rest_manager(&url, fast_retry_policy(3)).goldpass().await;
+    assert!(result.is_ok(), "Goldpass request should succeed after retries: {:#?}", result.err());
+    throttled.assert_async().await;
+    success.assert_async().await;
+}
+
+#[tokio::test]
+async fn retry_gives_up_after_max_attempts_test() {
+    let mut server = mockito::Server::new_async().await;
+    let url = server.url();
+    let maintenance = server
+        .mock("GET", "/goldpass/seasons/current")
+        .with_status(503)
+        .expect(2)
+        .create_async()
+        .await;
+    let result = get_retry_rest_manager(&url, fast_retry_policy(2)).goldpass().await;
+    match result {
+        Err(err) => assert_eq!(err.api_reason(), Some(&ApiErrorReason::InMaintenance)),
+        other => panic!("Expected maintenance error, got {:#?}", other),
+    }
+    maintenance.assert_async().await;
+}
+
+#[tokio::test]
+async fn retry_skips_non_retryable_reason_test() {
+    let mut server = mockito::Server::new_async().await;
+    let url = server.url();
+    let not_found = server
+        .mock("GET", "/goldpass/seasons/current")
+        .with_status(404)
+        .expect(1)
+        .create_async()
+        .await;
+    let result = get_retry_rest_manager(&url, fast_retry_policy(3)).goldpass().await;
+    assert!(result.is_err(), "Not found response should be returned as an error");
+    not_found.assert_async().await;
+}
+
+#[tokio::test]
+async fn retry_after_longer_than_max_backoff_test() {
+    let mut server = mockito::Server::new_async().await;
+    let url = server.url();
+    let throttled = server
+        .mock("GET", "/goldpass/seasons/current")
+        .with_status(429)
+        .with_header("retry-after", "120")
+        .expect(1)
+        .create_async()
+        .await;
+    let result = get_retry_rest_manager(&url, fast_retry_policy(3)).goldpass().await;
+    assert!(result.is_err(), "Request should fail without waiting for the Retry-After delay");
+    throttled.assert_async().await;
+}
+
+#[test]
+fn retry_backoff_test() {
+    let policy = RetryPolicy::builder()
+        .initial_backoff(Duration::from_millis(100))
+        .max_backoff(Duration::from_millis(300))
+        .multiplier(2)
+        .jitter(false)
+        .build();
+    assert_eq!(policy.backoff(1, None), Some(Duration::from_millis(100)));
+    assert_eq!(policy.backoff(2, None), Some(Duration::from_millis(200)));
+    assert_eq!(policy.backoff(3, None), Some(Duration::from_millis(300)));
+    assert_eq!(policy.backoff(1, Some(Duration::from_millis(250))), Some(Duration::from_millis(250)));
+    assert_eq!(policy.backoff(1, Some(Duration::from_secs(1))), None);
+
+    let jittered = RetryPolicy::builder()
+        .initial_backoff(Duration::from_millis(100))
+        .jitter(true)
+        .build();
+    for _ in 0..32 {
+        let delay = jittered.backoff(1, None).expect("Delay should be computed");
+        assert!(delay >= Duration::from_millis(50) && delay <= Duration::from_millis(100), "Jittered delay out of range: {:?}", delay);
+    }
+}
@@ -0,0 +1,45 @@
+use clash_forge::api::common::tags::PlayerTag;
+use clash_forge::api::common::utils::{id_to_tag, tag_to_id, tag_to_u64, u64_to_tag};
+use proptest::prelude::*;
+
+#[test]
+fn tag_to_id_test() {
+    assert_eq!(tag_to_id("#2PP").expect("Tag should be valid"), (0, 1));
+    assert_eq!(tag_to_id("2pp").expect("Tag should be valid"), (0, 1));
+    assert_eq!(id_to_tag(0, 1).expect("ID should be valid"), "#2PP");
+    assert_eq!(id_to_tag(0, 0).expect("ID should be valid"), "#0");
+    assert_eq!(tag_to_u64("#2PP").expect("Tag should be valid"), 1);
+
+    let tag = PlayerTag::parse("#8VURQ0YUJ").expect("Tag should be valid");
+    let id = tag.to_u64().expect("Tag should fit into an ID");
+    assert_eq!(PlayerTag::from_u64(id).expect("ID should be valid"), tag);
+}
+
+#[test]
+fn tag_to_id_invalid_test() {
+    assert!(tag_to_id("#ABC").is_err(), "Tag with invalid characters should be rejected");
+    assert!(tag_to_id("#").is_err(), "Empty tag should be rejected");
+    assert!(tag_to_id("#VVVVVVVVVVVV").is_err(), "Tag that does not fit into an ID should be rejected");
+    assert!(id_to_tag(256, 0).is_err(), "High part above 255 should be rejected");
+}
+
+proptest! {
+    #[test]
+    fn id_round_trip(high in 0u32..=255, low in any::<u32>()) {
+        let tag = id_to_tag(high, low).unwrap();
+        prop_assert_eq!(tag_to_id(&tag).unwrap(), (high, low));
+    }
+
+    #[test]
+    fn u64_round_trip(high in 0u64..=255, low in any::<u32>()) {
+        let id = (high << 32) | low as u64;
+        let tag = u64_to_tag(id).unwrap();
+        prop_assert_eq!(tag_to_u64(&tag).unwrap(), id);
+    }
+
+    #[test]
+    fn tag_round_trip(tag in "[289PYLQGRJCUV][0289PYLQGRJCUV]{0,9}") {
+        let (high, low) = tag_to_id(&tag).unwrap();
+        prop_assert_eq!(id_to_tag(high, low).unwrap(), format!("#{tag}"));
+    }
+}
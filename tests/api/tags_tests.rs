@@ -0,0 +1,66 @@
+use clash_forge::api::clans::models::APIClanMember;
+use clash_forge::api::common::tags::{ClanTag, PlayerTag, WarTag};
+use clash_forge::api::common::utils::normalize_tag;
+use crate::api::utils::get_in_memory_rest_manager;
+use clash_forge::api::transport::InMemoryTransport;
+use clash_forge::errors::Error;
+use std::sync::Arc;
+
+#[test]
+fn tag_parse_test() {
+    let tag = PlayerTag::parse("#2PP").expect("Tag should be valid");
+    assert_eq!(tag.as_str(), "#2PP");
+    assert_eq!(tag.encoded(), "%232PP");
+    assert_eq!(tag.to_string(), "#2PP");
+
+    assert_eq!(ClanTag::parse(" 2pp ").expect("Tag should be valid"), "#2PP");
+    assert_eq!("8vurqoyuj".parse::<PlayerTag>().expect("Tag should be valid"), "#8VURQ0YUJ");
+    assert_eq!(WarTag::parse("#0").expect("Tag should be valid"), "#0");
+}
+
+#[test]
+fn tag_parse_invalid_test() {
+    for tag in ["#ABC", "", "#", "#2PP!", "DAY1TAG"] {
+        let result = PlayerTag::parse(tag);
+        assert!(matches!(result, Err(Error::InvalidTag(_))), "Tag {tag:?} should be invalid, got {result:?}");
+    }
+}
+
+#[test]
+fn tag_serde_test() {
+    let tag: ClanTag = serde_json::from_str(r##""#2q0q0jg82""##).expect("Tag should deserialize");
+    assert_eq!(tag, "#2Q0Q0JG82");
+    assert_eq!(serde_json::to_string(&tag).expect("Tag should serialize"), r##""#2Q0Q0JG82""##);
+
+    assert!(tag.is_valid());
+
+    let tag: ClanTag = serde_json::from_str(r##""#ABC""##).expect("Invalid tag should be kept");
+    assert_eq!(tag, "#ABC");
+    assert!(!tag.is_valid());
+}
+
+#[tokio::test]
+async fn invalid_tag_is_not_requested_test() {
+    let transport = Arc::new(InMemoryTransport::new());
+    let rest_manager = get_in_memory_rest_manager(transport.clone());
+
+    let result = rest_manager.player("#ABC").await;
+    assert!(matches!(result, Err(Error::InvalidTag(_))), "Expected invalid tag error, got {result:#?}");
+    assert!(rest_manager.clan_members_paginated("#ABC").is_err());
+    assert!(transport.requests().is_empty(), "Invalid tags should not be sent to the API");
+}
+
+#[test]
+fn invalid_tag_in_response_test() {
+    let mut member = crate::api::utils::get_mock_json("clans/clan_members/2Q0Q0JG82.json")["items"][0].clone();
+    member["tag"] = "#NEW-FORMAT".into();
+    let member: APIClanMember = serde_json::from_value(member).expect("An unexpected tag should not fail the response");
+    assert_eq!(member.tag, "#NEW-FORMAT");
+    assert!(!member.tag.is_valid());
+}
+
+#[test]
+fn normalize_tag_test() {
+    assert_eq!(normalize_tag("2po"), "%232P0");
+    assert_eq!(normalize_tag("#2PP"), "%232PP");
+}
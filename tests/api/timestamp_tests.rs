@@ -0,0 +1,59 @@
+use clash_forge::api::common::timestamp::APITimestamp;
+use clash_forge::api::goldpass::models::APIGoldPassSeason;
+
+#[test]
+fn timestamp_serde_test() {
+    let season: APIGoldPassSeason =
+        serde_json::from_str(r#"{"startTime":"20250601T080000.000Z","endTime":"20250701T080000.000Z"}"#).expect("Season should deserialize");
+    assert_eq!(season.start_time.as_str(), "20250601T080000.000Z");
+    assert!(season.start_time < season.end_time);
+
+    let json = serde_json::to_value(&season).expect("Season should serialize");
+    assert_eq!(json["endTime"], "20250701T080000.000Z");
+    assert_eq!(season.end_time, APITimestamp::parse("20250701T080000.000Z").expect("Timestamp should be valid"));
+}
+
+#[cfg(feature = "chrono")]
+mod chrono_tests {
+    use super::*;
+    use chrono::{TimeDelta, TimeZone, Utc};
+    use clash_forge::api::clans::models::{APIClanWar, WarPhase};
+
+    fn get_war(state: &str) -> APIClanWar {
+        let mut war = crate::api::utils::get_mock_json("clans/current_war/QY9RQ2G2_inWar.json");
+        war["state"] = state.into();
+        war["preparationStartTime"] = "20250101T080000.000Z".into();
+        war["startTime"] = "20250102T080000.000Z".into();
+        war["endTime"] = "20250103T080000.000Z".into();
+        serde_json::from_value(war).expect("War should deserialize")
+    }
+
+    #[test]
+    fn timestamp_datetime_test() {
+        let timestamp = APITimestamp::parse("20250101T080000.000Z").expect("Timestamp should be valid");
+        assert_eq!(timestamp.datetime(), Utc.with_ymd_and_hms(2025, 1, 1, 8, 0, 0).unwrap());
+        assert_eq!(APITimestamp::from(timestamp.datetime()), timestamp);
+        assert!(APITimestamp::parse("2025-01-01T08:00:00Z").is_err(), "Other formats should be rejected");
+    }
+
+    #[test]
+    fn war_phase_test() {
+        let war = get_war("inWar");
+        let at = |day, hour| Utc.with_ymd_and_hms(2025, 1, day, hour, 0, 0).unwrap();
+        assert_eq!(war.phase_at(at(1, 20)), WarPhase::Preparation);
+        assert_eq!(war.time_remaining_at(at(1, 20)), Some(TimeDelta::hours(12)));
+        assert_eq!(war.phase_at(at(2, 8)), WarPhase::Battle);
+        assert_eq!(war.time_remaining_at(at(3, 6)), Some(TimeDelta::hours(2)));
+        assert_eq!(war.phase_at(at(4, 0)), WarPhase::Ended);
+        assert_eq!(war.time_remaining_at(at(4, 0)), None);
+        assert_eq!(get_war("notInWar").phase_at(at(2, 8)), WarPhase::NotInWar);
+    }
+
+    #[test]
+    fn goldpass_days_left_test() {
+        let season: APIGoldPassSeason =
+            serde_json::from_str(r#"{"startTime":"20250601T080000.000Z","endTime":"20250701T080000.000Z"}"#).expect("Season should deserialize");
+        assert_eq!(season.days_left_at(Utc.with_ymd_and_hms(2025, 6, 20, 20, 0, 0).unwrap()), 10);
+        assert_eq!(season.days_left_at(Utc.with_ymd_and_hms(2025, 7, 2, 0, 0, 0).unwrap()), 0);
+    }
+}
@@ -0,0 +1,64 @@
+use crate::api::utils::get_in_memory_config;
+use clash_forge::api::rest_manager::{RestManager, RestManagerConfig};
+use clash_forge::api::retry::RetryPolicy;
+use clash_forge::api::transport::{HttpResponse, InMemoryTransport};
+use reqwest::Method;
+use std::sync::Arc;
+use std::time::Duration;
+
+fn get_in_memory_rest_manager(transport: Arc<InMemoryTransport>) -> RestManager {
+    let config = RestManagerConfig {
+        retry_policy: RetryPolicy::builder().initial_backoff(Duration::from_millis(1)).jitter(false).build(),
+        ..get_in_memory_config(transport)
+    };
+    RestManager::with_config("test_token", config).expect("Test object should be created")
+}
+
+#[tokio::test]
+async fn in_memory_transport_test() {
+    let transport = Arc::new(InMemoryTransport::new());
+    let body = std::fs::read(crate::api::utils::get_mock_data_path("goldpass/200.json")).expect("Mock data should exist");
+    transport.push_response(Method::GET, "goldpass/seasons/current", HttpResponse::new(200, body));
+
+    let result = get_in_memory_rest_manager(transport.clone()).goldpass().await;
+    assert!(result.is_ok(), "Goldpass request returned an error: {:#?}", result.err());
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, Method::GET);
+    assert_eq!(requests[0].url, "https://api.clashofclans.com/v1/goldpass/seasons/current");
+    assert_eq!(requests[0].headers.get("authorization").map(|value| value.to_str().unwrap()), Some("Bearer test_token"));
+}
+
+#[tokio::test]
+async fn in_memory_transport_queued_responses_test() {
+    let transport = Arc::new(InMemoryTransport::new());
+    transport.push_response(
+        Method::POST,
+        "players/%238VURQ0YUJ/verifytoken",
+        HttpResponse::new(503, r#"{"reason":"inMaintenance","message":"Maintenance"}"#),
+    );
+    transport.push_response(
+        Method::POST,
+        "players/%238VURQ0YUJ/verifytoken",
+        HttpResponse::new(200, r##"{"tag":"#8VURQ0YUJ","token":"valid_token","status":"ok"}"##),
+    );
+
+    let result = get_in_memory_rest_manager(transport.clone()).verify("8VURQOYUJ", "valid_token").await;
+    assert!(result.is_ok_and(|verified| verified), "Token should be verified after the retry");
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 2);
+    let body: serde_json::Value = serde_json::from_slice(requests[1].body.as_deref().expect("Should have a body")).expect("Body should be JSON");
+    assert_eq!(body, serde_json::json!({"token": "valid_token"}));
+}
+
+#[tokio::test]
+async fn in_memory_transport_not_found_test() {
+    let transport = Arc::new(InMemoryTransport::new());
+    let result = get_in_memory_rest_manager(transport).player("8VURQOYUJ").await;
+    match result {
+        Err(err) => assert!(err.is_not_found(), "Expected not found error, got {:#?}", err),
+        other => panic!("Expected not found error, got {:#?}", other),
+    }
+}
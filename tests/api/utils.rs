@@ -1,18 +1,58 @@
+use clash_forge::api::rest_manager::{RestManager, RestManagerConfig};
+use clash_forge::api::retry::RetryPolicy;
+use clash_forge::api::transport::{HttpResponse, InMemoryTransport};
+use serde_json::Value;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 pub fn get_mock_data_path(path: impl AsRef<Path>) -> PathBuf {
     let current_dir = std::env::current_dir().expect("Should be ok");
     current_dir.join("tests/api/mock_data").join(path.as_ref())
 }
 
-pub fn get_test_rest_manager(base_url: &str) -> clash_forge::api::rest_manager::RestManager {
-    clash_forge::api::rest_manager::RestManager::with_config(
-        "test_token",
-        clash_forge::api::rest_manager::RestManagerConfig {
-            base_url: base_url.to_string(),
-            timeout: std::time::Duration::from_secs(30),
-            user_agent: format!("clash-forge-tests/{}", env!("CARGO_PKG_VERSION")),
-        },
-    )
-    .expect("Test object should be created")
-}
\ No newline at end of file
+/// Reads a mock response as JSON.
+pub fn get_mock_json(path: impl AsRef<Path>) -> Value {
+    let path = get_mock_data_path(path);
+    serde_json::from_str(&std::fs::read_to_string(path).expect("Mock data should exist")).expect("Mock data should be JSON")
+}
+
+pub fn get_test_config(base_url: &str) -> RestManagerConfig {
+    RestManagerConfig {
+        base_url: base_url.to_string(),
+        timeout: std::time::Duration::from_secs(30),
+        user_agent: format!("clash-forge-tests/{}", env!("CARGO_PKG_VERSION")),
+        ..Default::default()
+    }
+}
+
+pub fn get_test_rest_manager(base_url: &str) -> RestManager {
+    RestManager::with_config("test_token", get_test_config(base_url)).expect("Test object should be created")
+}
+
+/// Configuration sending requests to the transport, without retries.
+pub fn get_in_memory_config(transport: Arc<InMemoryTransport>) -> RestManagerConfig {
+    RestManagerConfig {
+        transport: Some(transport),
+        retry_policy: RetryPolicy::disabled(),
+        ..Default::default()
+    }
+}
+
+pub fn get_in_memory_rest_manager(transport: Arc<InMemoryTransport>) -> RestManager {
+    RestManager::with_config("test_token", get_in_memory_config(transport)).expect("Test object should be created")
+}
+
+/// Page of labels with the given IDs.
+pub fn labels_page(ids: &[u32], after: Option<&str>) -> HttpResponse {
+    labels_page_with_cursors(ids, after, None)
+}
+
+pub fn labels_page_with_cursors(ids: &[u32], after: Option<&str>, before: Option<&str>) -> HttpResponse {
+    let items: Vec<_> = ids
+        .iter()
+        .map(|id| format!(r#"{{"id":{id},"name":"Label {id}","iconUrls":{{"small":"small.png","medium":"medium.png"}}}}"#))
+        .collect();
+    let cursor = |cursor: Option<&str>| cursor.map_or("null".to_string(), |cursor| format!(r#""{cursor}""#));
+    let (after, before) = (cursor(after), cursor(before));
+    HttpResponse::new(200, format!(r#"{{"items":[{}],"paging":{{"cursors":{{"after":{after},"before":{before}}}}}}}"#, items.join(",")))
+}
@@ -0,0 +1,109 @@
+use crate::events::utils::{from_json, get_in_memory_rest_manager, get_mock_json};
+use clash_forge::api::clans::models::{APIClan, APIWarFrequency};
+use clash_forge::api::transport::{HttpResponse, InMemoryTransport};
+use clash_forge::events::clan::{diff_clan, ClanEvent, ClanPoller};
+use reqwest::Method;
+use serde_json::json;
+use std::sync::Arc;
+
+const CLAN_PATH: &str = "clans/%232Q0Q0JG82";
+
+fn get_mock_clan() -> serde_json::Value {
+    get_mock_json("clans/clan_info/2Q0Q0JG82.json")
+}
+
+/// The clan after every tracked property changed.
+fn get_changed_clan() -> serde_json::Value {
+    let mut clan = get_mock_clan();
+    clan["description"] = "New description".into();
+    let labels = clan["labels"].as_array_mut().expect("Labels should be an array");
+    let mut label = labels.remove(0);
+    label["id"] = 56000002.into();
+    label["name"] = "Trophy Pushing".into();
+    labels.push(label);
+    clan["requiredTrophies"] = 3400.into();
+    clan["requiredTownhallLevel"] = 14.into();
+    clan["warFrequency"] = "oncePerWeek".into();
+    clan["isWarLogPublic"] = false.into();
+    clan["warLeague"] = json!({ "id": 48000018, "name": "Champion League I" });
+    clan["capitalLeague"] = json!({ "id": 85000018, "name": "Champion League I" });
+    clan["clanLevel"] = 17.into();
+    clan["location"] = json!({ "id": 32000249, "name": "United States", "isCountry": true, "countryCode": "US" });
+    clan["chatLanguage"] = json!({ "id": 75000001, "name": "Français", "languageCode": "FR" });
+    clan["clanCapital"]["districts"][1]["districtHallLevel"] = 6.into();
+    clan
+}
+
+#[test]
+fn diff_clan_test() {
+    let previous: APIClan = from_json(&get_mock_clan());
+    let current: APIClan = from_json(&get_changed_clan());
+
+    let events = diff_clan(&previous, &current);
+    assert_eq!(events.len(), 12, "Unexpected events: {events:#?}");
+    assert!(matches!(&events[0], ClanEvent::DescriptionChanged { to: Some(to), .. } if to == "New description"));
+    assert!(matches!(&events[1], ClanEvent::LabelsChanged { added, removed, .. } if added[0].id == 56000002 && removed[0].id == 56000000));
+    assert!(matches!(events[2], ClanEvent::RequiredTrophiesChanged { from: 3200, to: 3400, .. }));
+    assert!(matches!(events[3], ClanEvent::RequiredTownHallLevelChanged { from: 13, to: 14, .. }));
+    assert!(matches!(events[4], ClanEvent::WarFrequencyChanged { from: APIWarFrequency::Always, to: APIWarFrequency::OncePerWeek, .. }));
+    assert!(matches!(events[5], ClanEvent::WarLogVisibilityChanged { is_war_log_public: false, .. }));
+    assert!(matches!(&events[6], ClanEvent::WarLeagueChanged { to, .. } if to.id == 48000018));
+    assert!(matches!(&events[7], ClanEvent::CapitalLeagueChanged { to: Some(to), .. } if to.id == 85000018));
+    assert!(matches!(events[8], ClanEvent::ClanLevelChanged { from: 16, to: 17, .. }));
+    assert!(matches!(&events[9], ClanEvent::LocationChanged { to: Some(to), .. } if to.id == 32000249));
+    assert!(matches!(&events[10], ClanEvent::ChatLanguageChanged { to: Some(to), .. } if to.id == 75000001));
+    assert!(matches!(&events[11], ClanEvent::DistrictHallUpgraded { district, from: 5, to: 6, .. } if district.id == 70000001));
+    assert!(events.iter().all(|event| *event.clan() == current.tag));
+    assert!(diff_clan(&current, &current).is_empty());
+}
+
+#[test]
+fn diff_clan_unchanged_ids_test() {
+    let previous: APIClan = from_json(&get_mock_clan());
+    let mut json = get_mock_clan();
+    // renamed entries with the same IDs are not changes of the clan
+    json["location"]["localizedName"] = "Internationale".into();
+    json["warLeague"]["name"] = "Renamed".into();
+    json["labels"][0]["name"] = "Renamed".into();
+    let current: APIClan = from_json(&json);
+
+    let events = diff_clan(&previous, &current);
+    assert!(events.is_empty(), "Unexpected events: {events:#?}");
+}
+
+#[test]
+fn diff_clan_missing_capital_league_test() {
+    let mut json = get_mock_clan();
+    json.as_object_mut().expect("Clan should be an object").remove("capitalLeague");
+    let previous: APIClan = from_json(&json);
+    assert!(diff_clan(&previous, &previous).is_empty());
+
+    let current: APIClan = from_json(&get_mock_clan());
+    let events = diff_clan(&previous, &current);
+    assert!(matches!(&events[..], [ClanEvent::CapitalLeagueChanged { from: None, to: Some(_), .. }]), "Unexpected events: {events:#?}");
+}
+
+#[tokio::test]
+async fn clan_poller_test() {
+    let transport = Arc::new(InMemoryTransport::new());
+    transport.push_response(Method::GET, CLAN_PATH, HttpResponse::new(200, get_mock_clan().to_string()));
+    transport.push_response(Method::GET, CLAN_PATH, HttpResponse::new(200, get_changed_clan().to_string()));
+    let rest_manager = get_in_memory_rest_manager(transport);
+
+    let mut poller = ClanPoller::builder().clan("#2Q0Q0JG82").build().expect("Tags should be valid");
+    assert!(poller.poll(&rest_manager).await.is_empty(), "The first poll should only record the clan");
+    let events: Vec<_> = poller.poll(&rest_manager).await.into_iter().map(|event| event.expect("Clan should be retrieved")).collect();
+    assert_eq!(events.len(), 12, "Unexpected events: {events:#?}");
+    assert!(poller.poll(&rest_manager).await.is_empty());
+}
+
+#[tokio::test]
+async fn clan_poller_error_test() {
+    let rest_manager = get_in_memory_rest_manager(Arc::new(InMemoryTransport::new()));
+
+    let mut poller = ClanPoller::builder().clans(["#2Q0Q0JG82"]).build().expect("Tags should be valid");
+    let events = poller.poll(&rest_manager).await;
+    assert_eq!(events.len(), 1);
+    assert!(events[0].is_err());
+    assert!(ClanPoller::builder().clan("not a tag").build().is_err());
+}
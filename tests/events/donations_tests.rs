@@ -0,0 +1,177 @@
+use crate::events::utils::{from_json, get_in_memory_rest_manager, get_mock_members, page};
+use clash_forge::api::clans::models::APIClanMember;
+use clash_forge::api::common::tags::ClanTag;
+use clash_forge::api::transport::InMemoryTransport;
+use clash_forge::events::donations::{season_end, DonationEvent, DonationTotals, DonationTracker, SeasonDonations};
+use reqwest::Method;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const CLAN: &str = "#2Q0Q0JG82";
+const MEMBERS_PATH: &str = "clans/%232Q0Q0JG82/members";
+// 2026-10-18 00:00 UTC, the season ends on 2026-10-26 05:00 UTC
+const MID_SEASON: u64 = 1_792_281_600;
+const SEASON_END: u64 = 1_792_990_800;
+
+fn at(seconds: u64) -> SystemTime {
+    UNIX_EPOCH + Duration::from_secs(seconds)
+}
+
+/// Two members with the given donation counters.
+fn get_members(counters: [(i64, i64); 2]) -> Vec<APIClanMember> {
+    get_mock_members()
+        .into_iter()
+        .zip(counters)
+        .map(|(mut member, (donations, received))| {
+            member["donations"] = donations.into();
+            member["donationsReceived"] = received.into();
+            from_json(&member)
+        })
+        .collect()
+}
+
+fn get_season(counters: [(i64, i64); 2]) -> SeasonDonations {
+    SeasonDonations::new(ClanTag::parse(CLAN).expect("Tag should be valid"), &get_members(counters), at(MID_SEASON))
+}
+
+#[test]
+fn season_end_test() {
+    assert_eq!(season_end(at(MID_SEASON)), at(SEASON_END));
+    assert_eq!(season_end(at(SEASON_END - 1)), at(SEASON_END));
+    // 2026-11-30 05:00 UTC
+    assert_eq!(season_end(at(SEASON_END)), at(1_796_014_800));
+    // 2026-12-31 12:00 UTC ends on 2027-01-25 05:00 UTC
+    assert_eq!(season_end(at(1_798_718_400)), at(1_800_853_200));
+    // 2024-02-01 00:00 UTC ends on 2024-02-26 05:00 UTC
+    assert_eq!(season_end(at(1_706_745_600)), at(1_708_923_600));
+}
+
+#[test]
+fn donations_delta_test() {
+    let mut season = get_season([(100, 50), (20, 0)]);
+    let members = get_members([(130, 50), (20, 5)]);
+
+    let events = season.update(&members, at(MID_SEASON + 60));
+    assert_eq!(events.len(), 2, "Unexpected events: {events:#?}");
+    let first = DonationTotals { donated: 130, received: 50 };
+    assert!(matches!(&events[0], DonationEvent::Donated { member, amount: 30, totals, .. } if member.tag == members[0].tag && *totals == first));
+    assert!(matches!(&events[1], DonationEvent::Received { member, amount: 5, .. } if member.tag == members[1].tag));
+    assert_eq!(season.member_totals(&members[0].tag).ratio(), Some(2.6));
+    assert_eq!(season.member_totals(&members[1].tag).ratio(), Some(4.0));
+    assert!(season.update(&members, at(MID_SEASON + 120)).is_empty());
+}
+
+#[test]
+fn donations_rejoin_test() {
+    let mut season = get_season([(100, 50), (20, 0)]);
+    let members = get_members([(100, 50), (20, 0)]);
+    season.update(&members[1..], at(MID_SEASON + 60));
+
+    // the member rejoined and their counters started at zero
+    let events = season.update(&get_members([(10, 0), (20, 0)]), at(MID_SEASON + 120));
+    assert_eq!(events.len(), 1, "Unexpected events: {events:#?}");
+    assert!(matches!(events[0], DonationEvent::Donated { amount: 10, .. }));
+    assert_eq!(season.member_totals(&members[0].tag), DonationTotals { donated: 110, received: 50 });
+    assert_eq!(season.season_end(), at(SEASON_END));
+}
+
+#[test]
+fn donations_season_reset_test() {
+    let mut season = get_season([(100, 50), (20, 0)]);
+    let members = get_members([(100, 50), (20, 0)]);
+
+    // the counters were not reset yet
+    let events = season.update(&get_members([(110, 50), (20, 0)]), at(SEASON_END + 60));
+    assert!(matches!(events[..], [DonationEvent::Donated { amount: 10, .. }]), "Unexpected events: {events:#?}");
+    assert_eq!(season.season_end(), at(SEASON_END));
+
+    let events = season.update(&get_members([(4, 0), (0, 2)]), at(SEASON_END + 120));
+    assert_eq!(events.len(), 3, "Unexpected events: {events:#?}");
+    match &events[0] {
+        DonationEvent::SeasonEnded { totals, .. } => {
+            assert_eq!(totals[&members[0].tag], DonationTotals { donated: 110, received: 50 });
+            assert_eq!(totals[&members[1].tag], DonationTotals { donated: 20, received: 0 });
+        }
+        other => panic!("Expected the end of the season, got {other:#?}"),
+    }
+    assert!(matches!(events[1], DonationEvent::Donated { amount: 4, totals: DonationTotals { donated: 4, received: 0 }, .. }));
+    assert!(matches!(events[2], DonationEvent::Received { amount: 2, .. }));
+    assert_eq!(season.season_end(), at(1_796_014_800));
+    assert_eq!(season.member_totals(&members[1].tag).ratio(), Some(0.0));
+}
+
+#[test]
+fn donations_late_season_reset_test() {
+    let mut season = get_season([(100, 50), (20, 0)]);
+
+    // a day after the end the season ends even without a drop, and only the changes since the last poll are counted
+    let members = get_members([(150, 60), (20, 0)]);
+    let events = season.update(&members, at(SEASON_END + 24 * 60 * 60));
+    assert_eq!(events.len(), 3, "Unexpected events: {events:#?}");
+    assert!(matches!(events[0], DonationEvent::SeasonEnded { .. }));
+    assert!(matches!(events[1], DonationEvent::Donated { amount: 50, totals: DonationTotals { donated: 50, received: 10 }, .. }));
+    assert!(matches!(events[2], DonationEvent::Received { amount: 10, .. }));
+    assert_eq!(season.member_totals(&members[1].tag), DonationTotals::default());
+
+    // a reset reported after that is attributed to rejoins of the members
+    let events = season.update(&get_members([(5, 0), (0, 0)]), at(SEASON_END + 25 * 60 * 60));
+    assert!(matches!(events[..], [DonationEvent::Donated { amount: 5, .. }]), "Unexpected events: {events:#?}");
+    assert_eq!(season.member_totals(&members[0].tag), DonationTotals { donated: 55, received: 10 });
+}
+
+#[tokio::test]
+async fn donation_tracker_test() {
+    let mut members = get_mock_members();
+    let transport = Arc::new(InMemoryTransport::new());
+    transport.push_response(Method::GET, MEMBERS_PATH, page(&members));
+    members[0]["donations"] = (members[0]["donations"].as_i64().expect("Donations should be a number") + 7).into();
+    transport.push_response(Method::GET, MEMBERS_PATH, page(&members));
+    let rest_manager = get_in_memory_rest_manager(transport);
+
+    let mut tracker = DonationTracker::builder().clan(CLAN).build().expect("Tags should be valid");
+    assert!(tracker.poll(&rest_manager).await.is_empty(), "The first poll should only record the members");
+    let events: Vec<_> = tracker.poll(&rest_manager).await.into_iter().map(|event| event.expect("Members should be retrieved")).collect();
+    assert_eq!(events.len(), 1, "Unexpected events: {events:#?}");
+    assert!(matches!(&events[0], DonationEvent::Donated { amount: 7, .. }));
+
+    let clan = ClanTag::parse(CLAN).expect("Tag should be valid");
+    let season = tracker.season(&clan).expect("Clan should be tracked");
+    assert_eq!(season.totals().len(), members.len());
+    assert!(events.iter().all(|event| *event.clan() == clan));
+}
+
+#[test]
+fn donations_rejoin_after_season_end_test() {
+    let mut season = get_season([(100, 50), (20, 0)]);
+
+    // a single member whose counters dropped rejoined, the counters of the others were not reset yet
+    let events = season.update(&get_members([(10, 0), (25, 0)]), at(SEASON_END + 60));
+    assert!(events.iter().all(|event| !matches!(event, DonationEvent::SeasonEnded { .. })), "Unexpected events: {events:#?}");
+    assert_eq!(season.season_end(), at(SEASON_END));
+}
+
+#[test]
+fn donations_tracking_started_before_reset_test() {
+    let clan = ClanTag::parse(CLAN).expect("Tag should be valid");
+    // tracking starts after the end, but the counters still belong to the ended season
+    let mut season = SeasonDonations::new(clan, &get_members([(100, 50), (20, 10)]), at(SEASON_END + 60));
+    assert_eq!(season.season_end(), at(SEASON_END));
+
+    let events = season.update(&get_members([(4, 0), (0, 2)]), at(SEASON_END + 120));
+    assert!(matches!(events[0], DonationEvent::SeasonEnded { .. }), "Unexpected events: {events:#?}");
+    let members = get_members([(4, 0), (0, 2)]);
+    assert_eq!(season.member_totals(&members[0].tag), DonationTotals { donated: 4, received: 0 });
+    assert_eq!(season.member_totals(&members[1].tag), DonationTotals { donated: 0, received: 2 });
+    assert_eq!(season.season_end(), at(1_796_014_800));
+}
+
+#[test]
+fn donations_tracking_started_after_reset_test() {
+    let clan = ClanTag::parse(CLAN).expect("Tag should be valid");
+    // the counters were already reset when tracking started, so the new season is tracked
+    let mut season = SeasonDonations::new(clan, &get_members([(0, 0), (0, 0)]), at(SEASON_END + 60));
+    assert_eq!(season.season_end(), at(1_796_014_800));
+
+    let events = season.update(&get_members([(5, 0), (0, 0)]), at(SEASON_END + 24 * 60 * 60));
+    assert!(matches!(events[..], [DonationEvent::Donated { amount: 5, .. }]), "Unexpected events: {events:#?}");
+}
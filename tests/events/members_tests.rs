@@ -0,0 +1,93 @@
+use crate::events::utils::{from_json, get_in_memory_rest_manager, get_mock_members, next_event, page};
+use clash_forge::api::clans::models::{APIClanMember, APIClanMemberRole};
+use clash_forge::api::common::tags::ClanTag;
+use clash_forge::api::transport::{HttpResponse, InMemoryTransport};
+use clash_forge::events::members::{diff_members, MemberEvent, MemberPoller};
+use reqwest::Method;
+use std::sync::Arc;
+use std::time::Duration;
+
+const CLAN: &str = "#2Q0Q0JG82";
+const MEMBERS_PATH: &str = "clans/%232Q0Q0JG82/members";
+
+/// Members after one of them left, one was renamed, one promoted, one upgraded their town hall and one joined.
+fn get_changed_members() -> Vec<serde_json::Value> {
+    let mut members = get_mock_members();
+    let mut joined = members[0].clone();
+    joined["tag"] = "#2PP".into();
+    members.remove(0);
+    members[0]["name"] = "Renamed".into();
+    members[1]["role"] = "leader".into();
+    members[2]["townHallLevel"] = (members[2]["townHallLevel"].as_i64().expect("Town hall level should be a number") + 1).into();
+    members.push(joined);
+    members
+}
+
+#[test]
+fn diff_members_test() {
+    let previous: Vec<APIClanMember> = get_mock_members().iter().map(from_json).collect();
+    let current: Vec<APIClanMember> = get_changed_members().iter().map(from_json).collect();
+    let clan = ClanTag::parse(CLAN).expect("Tag should be valid");
+
+    let events = diff_members(&clan, &previous, &current);
+    assert_eq!(events.len(), 5, "Unexpected events: {events:#?}");
+    assert!(matches!(&events[0], MemberEvent::NameChanged { to, .. } if to == "Renamed"));
+    assert!(matches!(&events[1], MemberEvent::RoleChanged { from, to: APIClanMemberRole::Leader, .. } if *from == previous[2].role));
+    assert!(matches!(&events[2], MemberEvent::TownHallUpgraded { from, to, .. } if *to == from + 1));
+    assert!(matches!(&events[3], MemberEvent::MemberJoined { member, .. } if member.tag == "#2PP"));
+    assert!(matches!(&events[4], MemberEvent::MemberLeft { member, .. } if *member == previous[0]));
+    assert!(events.iter().all(|event| event.clan() == &clan));
+
+    assert!(diff_members(&clan, &current, &current).is_empty());
+}
+
+#[tokio::test]
+async fn member_poller_first_poll_test() {
+    let transport = Arc::new(InMemoryTransport::new());
+    transport.push_response(Method::GET, MEMBERS_PATH, page(&get_mock_members()));
+    transport.push_response(Method::GET, MEMBERS_PATH, page(&get_changed_members()));
+    let rest_manager = get_in_memory_rest_manager(transport);
+
+    let mut poller = MemberPoller::builder().clan(CLAN).build().expect("Tags should be valid");
+    assert!(poller.poll(&rest_manager).await.is_empty(), "The first poll should only record the members");
+    let events = poller.poll(&rest_manager).await;
+    assert_eq!(events.len(), 5);
+    assert!(events.iter().all(Result::is_ok));
+}
+
+#[tokio::test]
+async fn member_poller_spawn_test() {
+    let transport = Arc::new(InMemoryTransport::new());
+    transport.push_response(Method::GET, MEMBERS_PATH, page(&get_mock_members()));
+    transport.push_response(Method::GET, MEMBERS_PATH, HttpResponse::new(503, r#"{"reason":"inMaintenance"}"#));
+    transport.push_response(Method::GET, MEMBERS_PATH, page(&get_changed_members()));
+    let rest_manager = get_in_memory_rest_manager(transport.clone());
+
+    let mut receiver = MemberPoller::builder()
+        .clans([CLAN])
+        .interval(Duration::from_millis(10))
+        .build()
+        .expect("Tags should be valid")
+        .spawn(&rest_manager);
+    let err = next_event(&mut receiver).await.expect_err("Maintenance should be reported");
+    assert!(err.is_maintenance());
+    for _ in 0..5 {
+        next_event(&mut receiver).await.expect("Polling should continue after an error");
+    }
+    receiver.shutdown().await;
+    let polls = transport.requests().len();
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert_eq!(transport.requests().len(), polls, "Polling should stop after shutdown");
+}
+
+#[test]
+fn member_poller_invalid_tag_test() {
+    let err = MemberPoller::builder().clan(CLAN).clan("#INVALID").build().expect_err("Invalid tag should fail");
+    assert!(matches!(err, clash_forge::errors::Error::InvalidTag(_)));
+}
+
+#[test]
+fn member_poller_zero_interval_test() {
+    let err = MemberPoller::builder().clan(CLAN).interval(Duration::ZERO).build().expect_err("Zero interval should fail");
+    assert!(matches!(err, clash_forge::errors::Error::InvalidInterval));
+}
@@ -0,0 +1,7 @@
+pub mod utils;
+
+mod clan_tests;
+mod donations_tests;
+mod members_tests;
+mod players_tests;
+mod war_tests;
@@ -0,0 +1,109 @@
+use crate::events::utils::{from_json, get_in_memory_rest_manager, get_mock_json, next_event};
+use clash_forge::api::players::models::{APIPlayer, APIVillageType};
+use clash_forge::api::transport::{HttpResponse, InMemoryTransport};
+use clash_forge::events::players::{diff_player, ItemKind, PlayerEvent, PlayerWatcher};
+use reqwest::Method;
+use serde_json::Value;
+use std::sync::Arc;
+use std::time::Duration;
+
+const PLAYER_PATH: &str = "players/%238VURQ0YUJ";
+
+fn get_mock_player() -> Value {
+    get_mock_json("players/8VURQOYUJ.json")
+}
+
+fn increment(value: &mut Value) {
+    *value = (value.as_i64().expect("Value should be a number") + 1).into();
+}
+
+/// Index of an item of the home village.
+fn home_item(items: &Value, name: &str) -> usize {
+    items
+        .as_array()
+        .expect("Items should be an array")
+        .iter()
+        .position(|item| item["name"] == name && item["village"] == "home")
+        .expect("Item should exist")
+}
+
+/// The player after upgrading the halls and an item of every kind, activating a super troop and gaining a star.
+fn get_progressed_player() -> Value {
+    let mut player = get_mock_player();
+    increment(&mut player["townHallLevel"]);
+    increment(&mut player["townHallWeaponLevel"]);
+    increment(&mut player["builderHallLevel"]);
+    let baby_dragon = home_item(&player["troops"], "Baby Dragon");
+    increment(&mut player["troops"][baby_dragon]["level"]);
+    let super_barbarian = home_item(&player["troops"], "Super Barbarian");
+    player["troops"][super_barbarian]["superTroopIsActive"] = true.into();
+    increment(&mut player["heroes"][0]["level"]);
+    increment(&mut player["spells"][0]["level"]);
+    player["heroEquipment"].as_array_mut().expect("Equipment should be an array").remove(0);
+    let achievement = player["achievements"]
+        .as_array()
+        .expect("Achievements should be an array")
+        .iter()
+        .position(|achievement| achievement["stars"].as_i64() < Some(3))
+        .expect("An achievement should be incomplete");
+    increment(&mut player["achievements"][achievement]["stars"]);
+    player
+}
+
+#[test]
+fn diff_player_test() {
+    let previous: APIPlayer = from_json(&get_progressed_player());
+    let mut json = get_progressed_player();
+    // the equipment was unlocked
+    json["heroEquipment"].as_array_mut().expect("Equipment should be an array").insert(0, get_mock_player()["heroEquipment"][0].clone());
+    let current: APIPlayer = from_json(&json);
+    let events = diff_player(&previous, &current);
+    assert_eq!(events.len(), 1, "Unexpected events: {events:#?}");
+    assert!(matches!(&events[0], PlayerEvent::ItemUpgraded { kind: ItemKind::HeroEquipment, from: 0, .. }));
+
+    let previous: APIPlayer = from_json(&get_mock_player());
+    let current: APIPlayer = from_json(&get_progressed_player());
+    let events = diff_player(&previous, &current);
+    assert_eq!(events.len(), 8, "Unexpected events: {events:#?}");
+    assert!(matches!(events[0], PlayerEvent::TownHallUpgraded { from: 12, to: 13, .. }));
+    assert!(matches!(events[1], PlayerEvent::TownHallWeaponUpgraded { from: 5, to: 6, .. }));
+    assert!(matches!(events[2], PlayerEvent::BuilderHallUpgraded { from: 10, to: 11, .. }));
+    assert!(matches!(&events[3], PlayerEvent::ItemUpgraded { kind: ItemKind::Troop, name, village: APIVillageType::HomeVillage, from: 5, to: 6, .. } if name == "Baby Dragon"));
+    assert!(matches!(&events[4], PlayerEvent::SuperTroopActivated { name, .. } if name == "Super Barbarian"));
+    assert!(matches!(&events[5], PlayerEvent::ItemUpgraded { kind: ItemKind::Hero, name, to, .. } if *name == current.heroes[0].name && *to == current.heroes[0].level));
+    assert!(matches!(&events[6], PlayerEvent::ItemUpgraded { kind: ItemKind::Spell, name, .. } if *name == current.spells[0].name));
+    assert!(matches!(&events[7], PlayerEvent::AchievementStarGained { achievement, from, to, .. } if *to == from + 1 && achievement.stars == *to));
+    assert!(events.iter().all(|event| *event.player() == current));
+    assert!(diff_player(&current, &current).is_empty());
+}
+
+#[tokio::test]
+async fn player_watcher_test() {
+    let transport = Arc::new(InMemoryTransport::new());
+    transport.push_response(Method::GET, PLAYER_PATH, HttpResponse::new(200, get_mock_player().to_string()));
+    transport.push_response(Method::GET, PLAYER_PATH, HttpResponse::new(200, get_progressed_player().to_string()));
+    let rest_manager = get_in_memory_rest_manager(transport);
+
+    let mut watcher = PlayerWatcher::builder().players(["#8VURQ0YUJ", "#2PP"]).build().expect("Tags should be valid");
+    let events = watcher.poll(&rest_manager).await;
+    assert_eq!(events.len(), 1, "Only the missing player should fail");
+    assert!(events[0].is_err());
+    let events = watcher.poll(&rest_manager).await;
+    assert_eq!(events.iter().filter(|event| event.is_ok()).count(), 8);
+    assert!(events.last().is_some_and(Result::is_err), "Errors should come last");
+    assert!(PlayerWatcher::builder().player("not a tag").build().is_err());
+}
+
+#[tokio::test]
+async fn player_watcher_spawn_test() {
+    let transport = Arc::new(InMemoryTransport::new());
+    transport.push_response(Method::GET, PLAYER_PATH, HttpResponse::new(200, get_mock_player().to_string()));
+    transport.push_response(Method::GET, PLAYER_PATH, HttpResponse::new(200, get_progressed_player().to_string()));
+    let rest_manager = get_in_memory_rest_manager(transport);
+
+    let watcher = PlayerWatcher::builder().player("#8VURQ0YUJ").interval(Duration::from_millis(10)).build().expect("Tags should be valid");
+    let mut receiver = watcher.spawn(&rest_manager);
+    let event = next_event(&mut receiver).await.expect("Player should be retrieved");
+    assert!(matches!(event, PlayerEvent::TownHallUpgraded { .. }));
+    receiver.shutdown().await;
+}
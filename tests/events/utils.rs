@@ -0,0 +1,31 @@
+pub use crate::api::utils::{get_in_memory_rest_manager, get_mock_json};
+use clash_forge::api::transport::HttpResponse;
+use clash_forge::errors::Result;
+use clash_forge::events::poller::EventReceiver;
+use serde_json::Value;
+use std::time::Duration;
+
+/// Members of the clan `#2Q0Q0JG82` as JSON.
+pub fn get_mock_members() -> Vec<Value> {
+    let page = get_mock_json("clans/clan_members/2Q0Q0JG82.json");
+    page["items"].as_array().expect("Members should be an array").clone()
+}
+
+/// Converts JSON into a model.
+pub fn from_json<T: serde::de::DeserializeOwned>(value: &Value) -> T {
+    serde_json::from_value(value.clone()).expect("Mock data should match the model")
+}
+
+/// A single page response with the given items.
+pub fn page(items: &[Value]) -> HttpResponse {
+    let page = serde_json::json!({ "items": items, "paging": { "cursors": {} } });
+    HttpResponse::new(200, page.to_string())
+}
+
+/// Waits for the next event of a running poller.
+pub async fn next_event<E: Send + 'static>(receiver: &mut EventReceiver<E>) -> Result<E> {
+    tokio::time::timeout(Duration::from_secs(5), receiver.recv())
+        .await
+        .expect("Event should arrive")
+        .expect("Poller should run")
+}
@@ -0,0 +1,252 @@
+use crate::events::utils::{from_json, get_in_memory_rest_manager, get_mock_json};
+use clash_forge::api::clans::models::{APIClanWar, APIWarResult, APIWarState};
+use clash_forge::api::transport::{HttpResponse, InMemoryTransport};
+use clash_forge::events::war::{diff_war, WarCheckpoint, WarEvent, WarId, WarProgress, WarWatcher};
+use reqwest::Method;
+use std::sync::Arc;
+
+const WAR_PATH: &str = "clans/%23QY9RQ2G2/currentwar";
+
+fn get_mock_war() -> serde_json::Value {
+    get_mock_json("clans/current_war/QY9RQ2G2_inWar.json")
+}
+
+fn last_attack_order(war: &APIClanWar) -> i64 {
+    [&war.clan, &war.opponent]
+        .into_iter()
+        .flat_map(|clan| clan.members.iter().flatten())
+        .flat_map(|member| member.attacks.iter().flatten())
+        .map(|attack| attack.order)
+        .max()
+        .expect("War should have attacks")
+}
+
+fn get_progress(war: &APIClanWar, state: APIWarState, last_attack_order: i64) -> WarProgress {
+    WarProgress {
+        war: WarId::new(war, None).expect("War should have an opponent"),
+        state,
+        last_attack_order,
+    }
+}
+
+#[test]
+fn diff_war_attacks_test() {
+    let war: APIClanWar = from_json(&get_mock_war());
+    let last_order = last_attack_order(&war);
+    let mut progress = get_progress(&war, APIWarState::Preparation, last_order - 3);
+
+    let events = diff_war(&mut progress, &war);
+    assert!(matches!(&events[0], WarEvent::WarStateChanged { from: APIWarState::Preparation, to: APIWarState::InWar, .. }));
+    let orders: Vec<_> = events.iter().filter_map(WarEvent::attack).map(|attack| attack.order).collect();
+    assert!(orders.windows(2).all(|pair| pair[0] <= pair[1]), "Attacks should be reported in order: {orders:?}");
+    assert_eq!(orders.first(), Some(&(last_order - 2)));
+    assert_eq!(orders.last(), Some(&last_order));
+
+    let own_members = war.clan.members.as_ref().expect("Clan should have members");
+    for event in &events {
+        match event {
+            WarEvent::AttackMade { attacker, attack, .. } => {
+                assert_eq!(attacker.tag, attack.attacker_tag);
+                assert!(own_members.contains(attacker));
+            }
+            WarEvent::DefenseReceived { defender, attack, .. } => {
+                assert_eq!(defender.tag, attack.defender_tag);
+                assert!(own_members.contains(defender));
+            }
+            _ => {}
+        }
+    }
+    assert_eq!(progress.state, APIWarState::InWar);
+    assert_eq!(progress.last_attack_order, last_order);
+    assert!(diff_war(&mut progress, &war).is_empty(), "Reported attacks should not be repeated");
+}
+
+#[test]
+fn diff_war_best_opponent_attack_test() {
+    let war: APIClanWar = from_json(&get_mock_war());
+    let mut progress = get_progress(&war, APIWarState::InWar, 0);
+
+    let events = diff_war(&mut progress, &war);
+    let best_attacks = war.clan.members.iter().flatten().filter(|member| member.best_opponent_attack.is_some()).count();
+    let new_best: Vec<_> = events.iter().enumerate().filter(|(_, event)| matches!(event, WarEvent::NewBestOpponentAttack { .. })).collect();
+    assert_eq!(new_best.len(), best_attacks);
+    for (index, event) in new_best {
+        assert!(
+            matches!(&events[index - 1], WarEvent::DefenseReceived { attack, .. } if Some(attack) == event.attack()),
+            "New best attack should follow its defense"
+        );
+    }
+}
+
+#[test]
+fn diff_war_ended_test() {
+    let mut json = get_mock_war();
+    json["state"] = "warEnded".into();
+    let war: APIClanWar = from_json(&json);
+    let mut progress = get_progress(&war, APIWarState::InWar, last_attack_order(&war));
+
+    let events = diff_war(&mut progress, &war);
+    assert_eq!(events.len(), 2, "Unexpected events: {events:#?}");
+    assert!(matches!(&events[0], WarEvent::WarStateChanged { to: APIWarState::WarEnded, .. }));
+    match &events[1] {
+        WarEvent::WarEnded { clan, opponent, result, .. } => {
+            assert_eq!(clan.stars, war.clan.stars);
+            assert_eq!(opponent.stars, war.opponent.stars);
+            let expected = if war.clan.stars > war.opponent.stars { APIWarResult::Win } else { APIWarResult::Lose };
+            assert_eq!(*result, expected);
+        }
+        other => panic!("Expected the end of the war, got {other:#?}"),
+    }
+    assert!(diff_war(&mut progress, &war).is_empty());
+}
+
+#[tokio::test]
+async fn war_watcher_first_poll_test() {
+    let mut ended = get_mock_war();
+    ended["state"] = "warEnded".into();
+    let transport = Arc::new(InMemoryTransport::new());
+    transport.push_response(Method::GET, WAR_PATH, HttpResponse::new(200, get_mock_war().to_string()));
+    transport.push_response(Method::GET, WAR_PATH, HttpResponse::new(200, ended.to_string()));
+    let rest_manager = get_in_memory_rest_manager(transport);
+
+    let mut watcher = WarWatcher::builder().clan("#QY9RQ2G2").build().expect("Tags should be valid");
+    assert!(watcher.poll(&rest_manager).await.is_empty(), "The first poll should only record the war");
+    let events: Vec<_> = watcher.poll(&rest_manager).await.into_iter().map(|event| event.expect("War should be retrieved")).collect();
+    assert_eq!(events.len(), 2, "Unexpected events: {events:#?}");
+    assert!(matches!(events[1], WarEvent::WarEnded { .. }));
+}
+
+#[tokio::test]
+async fn war_watcher_resume_test() {
+    let war: APIClanWar = from_json(&get_mock_war());
+    let last_order = last_attack_order(&war);
+    let transport = Arc::new(InMemoryTransport::new());
+    transport.push_response(Method::GET, WAR_PATH, HttpResponse::new(200, get_mock_war().to_string()));
+    let rest_manager = get_in_memory_rest_manager(transport);
+
+    let checkpoint = WarCheckpoint {
+        clans: vec![war.clan.tag.clone().expect("Clan should have a tag")],
+        wars: vec![get_progress(&war, APIWarState::InWar, last_order - 1)],
+    };
+    let json = serde_json::to_string(&checkpoint).expect("Checkpoint should be serialized");
+    let mut checkpoint: WarCheckpoint = serde_json::from_str(&json).expect("Checkpoint should be deserialized");
+
+    let mut watcher = WarWatcher::builder().clan("#QY9RQ2G2").resume(checkpoint.clone()).build().expect("Tags should be valid");
+    let events: Vec<_> = watcher.poll(&rest_manager).await.into_iter().map(|event| event.expect("War should be retrieved")).collect();
+    assert!(!events.is_empty());
+    assert!(events.iter().all(|event| event.attack().map(|attack| attack.order) == Some(last_order)), "Unexpected events: {events:#?}");
+
+    for event in &events {
+        checkpoint.record(event);
+    }
+    assert_eq!(checkpoint.wars[0].last_attack_order, last_order);
+    assert_eq!(watcher.checkpoint(), checkpoint);
+}
+
+#[tokio::test]
+async fn war_watcher_league_wars_test() {
+    const CLAN: &str = "#2Q0Q0JG82";
+    const OWN_WAR_PATH: &str = "clanwarleagues/wars/%238LUYQJ2G9";
+    const FOREIGN_WAR_PATH: &str = "clanwarleagues/wars/%238LUYQRRQP";
+
+    let mut group = get_mock_json("clans/clan_war_league_group/2Q0Q0JG82_inWar.json");
+    group["rounds"] = serde_json::json!([
+        { "warTags": ["#8LUYQRRQP", "#8LUYQJ2G9"] },
+        { "warTags": ["#8LUYQP8UC", "#0"] },
+        { "warTags": ["#0", "#0"] },
+    ]);
+    // the watched clan is the opponent in this war
+    let mut own_war = get_mock_json("clans/clan_war_league_war/8LUYQJ2G9_inWar.json");
+    let clan = own_war["clan"].take();
+    own_war["clan"] = own_war["opponent"].take();
+    own_war["opponent"] = clan;
+    let mut ended_war = own_war.clone();
+    ended_war["state"] = "warEnded".into();
+    let mut foreign_war = get_mock_json("clans/clan_war_league_war/8LUYQP8UC_preparation.json");
+    foreign_war["clan"]["tag"] = "#2PP".into();
+
+    let transport = Arc::new(InMemoryTransport::new());
+    transport.push_response(Method::GET, "clans/%232Q0Q0JG82/currentwar", HttpResponse::new(200, get_mock_json("clans/current_war/2Q0Q0JG82_notInWar.json").to_string()));
+    transport.push_response(Method::GET, "clans/%232Q0Q0JG82/currentwar/leaguegroup", HttpResponse::new(200, group.to_string()));
+    transport.push_response(Method::GET, OWN_WAR_PATH, HttpResponse::new(200, own_war.to_string()));
+    transport.push_response(Method::GET, OWN_WAR_PATH, HttpResponse::new(200, ended_war.to_string()));
+    transport.push_response(Method::GET, FOREIGN_WAR_PATH, HttpResponse::new(200, foreign_war.to_string()));
+    transport.push_response(
+        Method::GET,
+        "clanwarleagues/wars/%238LUYQP8UC",
+        HttpResponse::new(200, get_mock_json("clans/clan_war_league_war/8LUYQP8UC_preparation.json").to_string()),
+    );
+    let rest_manager = get_in_memory_rest_manager(transport.clone());
+    let requests = |path: &str| transport.requests().iter().filter(|request| request.url.ends_with(path)).count();
+
+    let mut watcher = WarWatcher::builder().clan(CLAN).build().expect("Tags should be valid");
+    assert!(watcher.poll(&rest_manager).await.is_empty());
+    let checkpoint = watcher.checkpoint();
+    assert_eq!(checkpoint.wars.len(), 2);
+    assert!(checkpoint.wars.iter().all(|progress| progress.war.clan == CLAN && progress.war.war_tag.is_some()));
+
+    let events: Vec<_> = watcher.poll(&rest_manager).await.into_iter().map(|event| event.expect("Wars should be retrieved")).collect();
+    assert!(matches!(events.last(), Some(WarEvent::WarEnded { war, .. }) if war.war_tag.as_ref().is_some_and(|tag| *tag == "#8LUYQJ2G9")));
+    assert_eq!(requests(FOREIGN_WAR_PATH), 1, "Wars of other clans should be requested once");
+
+    assert!(watcher.poll(&rest_manager).await.is_empty());
+    assert_eq!(requests(OWN_WAR_PATH), 2, "Ended wars should not be requested again");
+}
+
+#[tokio::test]
+async fn war_watcher_disappeared_war_test() {
+    let mut war = get_mock_war();
+    war["clan"]["stars"] = 30.into();
+    let transport = Arc::new(InMemoryTransport::new());
+    transport.push_response(Method::GET, WAR_PATH, HttpResponse::new(200, war.to_string()));
+    // the next war started before a poll saw this one ended
+    let mut next_war = get_mock_war();
+    next_war["state"] = "preparation".into();
+    next_war["preparationStartTime"] = "20300101T080000.000Z".into();
+    for side in ["clan", "opponent"] {
+        for member in next_war[side]["members"].as_array_mut().expect("Members should be an array") {
+            member.as_object_mut().expect("Member should be an object").remove("attacks");
+        }
+    }
+    transport.push_response(Method::GET, WAR_PATH, HttpResponse::new(200, next_war.to_string()));
+    let rest_manager = get_in_memory_rest_manager(transport);
+
+    let mut watcher = WarWatcher::builder().clan("#QY9RQ2G2").league_wars(false).build().expect("Tags should be valid");
+    assert!(watcher.poll(&rest_manager).await.is_empty());
+    let events: Vec<_> = watcher.poll(&rest_manager).await.into_iter().map(|event| event.expect("War should be retrieved")).collect();
+    let ended = WarId::new(&from_json(&war), None).expect("War should have an opponent");
+    assert!(
+        events.iter().any(|event| matches!(event, WarEvent::WarEnded { war, clan, result: APIWarResult::Win, .. } if *war == ended && clan.stars == 30)),
+        "Unexpected events: {events:#?}"
+    );
+    assert_eq!(watcher.checkpoint().wars.len(), 1, "Ended wars should be forgotten");
+    assert!(watcher.poll(&rest_manager).await.is_empty());
+}
+
+#[tokio::test]
+async fn war_watcher_foreign_wars_pruned_test() {
+    const GROUP_PATH: &str = "clans/%232Q0Q0JG82/currentwar/leaguegroup";
+    const FOREIGN_WAR_PATH: &str = "clanwarleagues/wars/%238LUYQRRQP";
+
+    let mut group = get_mock_json("clans/clan_war_league_group/2Q0Q0JG82_inWar.json");
+    group["rounds"] = serde_json::json!([{ "warTags": ["#8LUYQRRQP"] }]);
+    let mut foreign_war = get_mock_json("clans/clan_war_league_war/8LUYQP8UC_preparation.json");
+    foreign_war["clan"]["tag"] = "#2PP".into();
+
+    let transport = Arc::new(InMemoryTransport::new());
+    transport.push_response(Method::GET, "clans/%232Q0Q0JG82/currentwar", HttpResponse::new(200, get_mock_json("clans/current_war/2Q0Q0JG82_notInWar.json").to_string()));
+    transport.push_response(Method::GET, GROUP_PATH, HttpResponse::new(200, group.to_string()));
+    transport.push_response(Method::GET, GROUP_PATH, HttpResponse::new(404, r#"{"reason":"notFound"}"#));
+    transport.push_response(Method::GET, GROUP_PATH, HttpResponse::new(200, group.to_string()));
+    transport.push_response(Method::GET, FOREIGN_WAR_PATH, HttpResponse::new(200, foreign_war.to_string()));
+    let rest_manager = get_in_memory_rest_manager(transport.clone());
+    let requests = || transport.requests().iter().filter(|request| request.url.ends_with(FOREIGN_WAR_PATH)).count();
+
+    let mut watcher = WarWatcher::builder().clan("#2Q0Q0JG82").build().expect("Tags should be valid");
+    assert!(watcher.poll(&rest_manager).await.is_empty());
+    assert_eq!(requests(), 1);
+    // the group is gone, so its wars are forgotten and requested again for a new group
+    assert!(watcher.poll(&rest_manager).await.is_empty());
+    assert!(watcher.poll(&rest_manager).await.is_empty());
+    assert_eq!(requests(), 2);
+}
@@ -1 +1,3 @@
 mod api;
+#[cfg(feature = "events")]
+mod events;